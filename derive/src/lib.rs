@@ -1,9 +1,13 @@
 use proc_macro2::TokenStream;
 //use proc_macro::TokenStream;
 //use syn::{parse_macro_input, DeriveInput};
-use quote::quote;
-use syn::{Attribute, Expr, Fields, Ident, Lit, Meta, MetaNameValue, NestedMeta, Pat, Type};
-use synstructure::decl_derive;
+use quote::{quote, quote_spanned};
+use syn::{
+    Attribute, Expr, Field, Fields, Ident, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Pat,
+    PatOr, Path, Token, Type, WherePredicate, parse::ParseStream, parse_quote,
+    punctuated::Punctuated, spanned::Spanned,
+};
+use synstructure::{BindingInfo, VariantInfo, decl_derive};
 
 #[derive(Default)]
 struct IdentCounter(usize);
@@ -23,22 +27,444 @@ impl IdentCounter {
     }
 }
 
+#[derive(Clone, Copy)]
+enum Derive {
+    Make,
+    Visit,
+    Analyze,
+}
+
+type Predicates = Punctuated<WherePredicate, Token![,]>;
+
+/// `bound = "..."` for every derive or `bound(make = "...", visit = "...", analyze = "...")`
+#[derive(Default)]
+struct BoundAttr {
+    make: Option<Predicates>,
+    visit: Option<Predicates>,
+    analyze: Option<Predicates>,
+}
+
+impl BoundAttr {
+    /// `None` if `item` isn't a `bound` attribute
+    fn parse(&mut self, item: &NestedMeta) -> Option<syn::Result<()>> {
+        match item {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(value),
+                ..
+            })) if path.is_ident("bound") => Some(self.set(None, value)),
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("bound") => {
+                Some(list.nested.iter().try_for_each(|nested| match nested {
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(value),
+                        ..
+                    })) if path.is_ident("make") => self.set(Some(Derive::Make), value),
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(value),
+                        ..
+                    })) if path.is_ident("visit") => self.set(Some(Derive::Visit), value),
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(value),
+                        ..
+                    })) if path.is_ident("analyze") => self.set(Some(Derive::Analyze), value),
+                    other => Err(syn::Error::new_spanned(
+                        other,
+                        "expected `make`, `visit` or `analyze` bound",
+                    )),
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, derive: Option<Derive>, value: &LitStr) -> syn::Result<()> {
+        let predicates: Predicates = value.parse_with(Punctuated::parse_terminated)?;
+        match derive {
+            Some(Derive::Make) => self.make = Some(predicates),
+            Some(Derive::Visit) => self.visit = Some(predicates),
+            Some(Derive::Analyze) => self.analyze = Some(predicates),
+            None => {
+                self.make = Some(predicates.clone());
+                self.visit = Some(predicates.clone());
+                self.analyze = Some(predicates);
+            }
+        }
+        Ok(())
+    }
+
+    fn get(&self, derive: Derive) -> Option<&Predicates> {
+        match derive {
+            Derive::Make => self.make.as_ref(),
+            Derive::Visit => self.visit.as_ref(),
+            Derive::Analyze => self.analyze.as_ref(),
+        }
+    }
+}
+
+/// where clause of a derived impl, field types are bounded unless `bound` overrides them
+struct Bounds<'a> {
+    derive: Derive,
+    infer: bool,
+    types: Vec<&'a Type>,
+    predicates: Vec<WherePredicate>,
+}
+
+impl<'a> Bounds<'a> {
+    fn new(derive: Derive, container: &ContainerAttrs) -> Self {
+        let predicates = container.bound.get(derive);
+        Self {
+            derive,
+            infer: predicates.is_none(),
+            types: vec![],
+            predicates: predicates.into_iter().flatten().cloned().collect(),
+        }
+    }
+
+    /// `typed` is false for fields that don't go through the field type, like `with`
+    fn add_field(&mut self, field: &'a Field, attrs: &FieldAttrs, typed: bool) {
+        if !self.infer {
+            return;
+        }
+        match (attrs.bound.get(self.derive), &attrs.recursive) {
+            (Some(predicates), _) => self.predicates.extend(predicates.iter().cloned()),
+            (None, Some(element)) => self.add_recursive(&field.ty, element, attrs.cycle),
+            (None, None) if typed && !self.types.contains(&&field.ty) => self.types.push(&field.ty),
+            (None, None) => {}
+        }
+    }
+
+    /// recursive fields are bounded on the container trait, so the impl doesn't require itself,
+    /// `cycle` leaves out the element too since requiring it would require the impl again
+    fn add_recursive(&mut self, ty: &Type, element: &Type, cycle: bool) {
+        let (container, made): (WherePredicate, WherePredicate) = match self.derive {
+            Derive::Make => (
+                parse_quote!(#ty: MakeRecursive<M, #element>),
+                parse_quote!(#element: MakeWith<M>),
+            ),
+            Derive::Visit => (
+                parse_quote!(#ty: VisitRecursive<V, #element>),
+                parse_quote!(#element: VisitWith<V>),
+            ),
+            Derive::Analyze => (
+                parse_quote!(#ty: AnalyzeRecursive<A, #element>),
+                parse_quote!(#element: AnalyzeWith<A>),
+            ),
+        };
+        self.predicates.push(container);
+        // `Self` is provided by the derived impl itself
+        if !cycle && !is_self(element) {
+            self.predicates.push(made);
+        }
+    }
+}
+
+fn is_self(ty: &Type) -> bool {
+    matches!(ty, Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("Self"))
+}
+
+/// `#[represent(...)]` attributes of a single field
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    default: Option<Expr>,
+    /// module with `make(&mut M) -> Result<T, M::Error>`,
+    /// `visit(&T, &mut V) -> Result<(), V::Error>` and, for `AnalyzeWith`,
+    /// `size(&T, &A) -> Result<usize, SizeOverflow>`
+    with: Option<Path>,
+    key: Option<LitStr>,
+    flatten: bool,
+    /// element type made through `MakeRecursive`, `Self` by default; the field is the container,
+    /// e.g. `Box<Self>`, `Optional<Box<Self>>`, `Arr<Self, LEN>` or `RepeatExt<Self, LEN>`
+    recursive: Option<Type>,
+    /// the recursive element leads back to this type, so it isn't required by the impl
+    cycle: bool,
+    bound: BoundAttr,
+}
+
+impl FieldAttrs {
+    fn from_field(field: &Field) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for item in get_nested(&field.attrs, "represent")? {
+            if let Some(res) = attrs.bound.parse(&item) {
+                res?;
+                continue;
+            }
+            match item {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    attrs.skip = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flatten") => {
+                    attrs.flatten = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("cycle") => {
+                    attrs.cycle = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("recursive") => {
+                    attrs.recursive = Some(parse_quote!(Self));
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(value),
+                    ..
+                })) if path.is_ident("default")
+                    || path.is_ident("with")
+                    || path.is_ident("key")
+                    || path.is_ident("recursive") =>
+                {
+                    if path.is_ident("default") {
+                        attrs.default = Some(value.parse()?);
+                    } else if path.is_ident("with") {
+                        attrs.with = Some(value.parse()?);
+                    } else if path.is_ident("recursive") {
+                        attrs.recursive = Some(value.parse()?);
+                    } else {
+                        attrs.key = Some(value);
+                    }
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unknown represent attribute, expected one of `skip`, `default`, `with`, `key`, `flatten`, `recursive`, `cycle`, `bound`",
+                    ));
+                }
+            }
+        }
+        if attrs.with.is_some() && (attrs.skip || attrs.default.is_some()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`with` can't be combined with `skip` or `default`",
+            ));
+        }
+        if attrs.recursive.is_some() && (attrs.with.is_some() || attrs.is_skipped()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`recursive` can't be combined with `with`, `skip` or `default`",
+            ));
+        }
+        if attrs.cycle && attrs.recursive.is_none() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`cycle` requires `recursive`",
+            ));
+        }
+        if attrs.flatten && attrs.key.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`key` can't be combined with `flatten`",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// `None` if the field is flattened into the parent
+    fn key(&self, counter: &mut IdentCounter, field: &Field) -> Option<TokenStream> {
+        let key = counter.next_key(&field.ident);
+        match &self.key {
+            _ if self.flatten => None,
+            Some(renamed) => Some(quote!(#renamed)),
+            None => Some(key),
+        }
+    }
+
+    /// field isn't encoded, `default` implies `skip`
+    fn is_skipped(&self) -> bool {
+        self.skip || self.default.is_some()
+    }
+}
+
+/// `#[represent(...)]` attributes of a struct or enum
+#[derive(Default)]
+struct ContainerAttrs {
+    transparent: bool,
+    bound: BoundAttr,
+}
+
+impl ContainerAttrs {
+    fn from_structure(s: &synstructure::Structure) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for item in get_nested(&s.ast().attrs, "represent")? {
+            if let Some(res) = attrs.bound.parse(&item) {
+                res?;
+                continue;
+            }
+            match item {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("transparent") => {
+                    attrs.transparent = true;
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unknown represent attribute, expected `transparent` or `bound`",
+                    ));
+                }
+            }
+        }
+        if attrs.transparent {
+            let encoded = match &s.ast().data {
+                syn::Data::Struct(data) => data
+                    .fields
+                    .iter()
+                    .map(FieldAttrs::from_field)
+                    .filter(|field| !matches!(field, Ok(field) if field.is_skipped()))
+                    .count(),
+                _ => 0,
+            };
+            if encoded != 1 {
+                return Err(syn::Error::new_spanned(
+                    &s.ast().ident,
+                    "`transparent` requires a struct with exactly one encoded field",
+                ));
+            }
+        }
+        Ok(attrs)
+    }
+
+    fn field_attrs(&self, field: &Field) -> syn::Result<FieldAttrs> {
+        let mut attrs = FieldAttrs::from_field(field)?;
+        attrs.flatten |= self.transparent;
+        Ok(attrs)
+    }
+}
+
+/// initializers of a made variant, `Default` for skipped fields
+///
+/// `tag` is the already made tag, it goes into the first field of an `#[alt(other)]` variant
+fn make_fields<'a>(
+    variant: &VariantInfo<'a>,
+    container: &ContainerAttrs,
+    bounds: &mut Bounds<'a>,
+    mut tag: Option<TokenStream>,
+) -> syn::Result<TokenStream> {
+    let mut counter = IdentCounter::default();
+    let mut values = vec![];
+    for binding in variant.bindings() {
+        let field = binding.ast();
+        let attrs = container.field_attrs(field)?;
+        let key = attrs.key(&mut counter, field);
+        let value = if let Some(tag) = tag.take() {
+            tag
+        } else if let Some(default) = attrs.default {
+            quote!(#default)
+        } else if attrs.skip {
+            quote!(Default::default())
+        } else if let Some(with) = &attrs.with {
+            bounds.add_field(field, &attrs, false);
+            match key {
+                Some(key) => quote!(maker.with_key(#key.into(), |maker| #with::make(maker))?),
+                None => quote!(#with::make(maker)?),
+            }
+        } else if let Some(element) = &attrs.recursive {
+            bounds.add_field(field, &attrs, false);
+            let ty = &field.ty;
+            let make = quote! {
+                <#ty as MakeRecursive<M, #element>>::make_recursive(maker, |maker| {
+                    maker.recurse(<#element as MakeWith<M>>::make_with)
+                })
+            };
+            match key {
+                Some(key) => quote!(maker.with_key(#key.into(), |maker| #make)?),
+                None => quote!(#make?),
+            }
+        } else {
+            bounds.add_field(field, &attrs, true);
+            match key {
+                Some(key) => quote!(maker.make_keyed(#key)?),
+                None => quote!(maker.make()?),
+            }
+        };
+        values.push(match &field.ident {
+            Some(ident) => quote!(#ident: #value),
+            None => value,
+        });
+    }
+    if tag.is_some() {
+        return Err(other_without_fields(variant));
+    }
+    Ok(match variant.ast().fields {
+        Fields::Named(_) => quote!({ #(#values,)* }),
+        Fields::Unnamed(_) => quote!(( #(#values,)* )),
+        Fields::Unit => quote!(),
+    })
+}
+
+fn expand(res: syn::Result<TokenStream>) -> TokenStream {
+    res.unwrap_or_else(|err| err.to_compile_error())
+}
+
 fn visit_derive(s: synstructure::Structure) -> TokenStream {
+    expand(try_visit_derive(s))
+}
+
+fn try_visit_derive(mut s: synstructure::Structure) -> syn::Result<TokenStream> {
+    s.underscore_const(true);
+    let struct_alt = StructAlt::from_attrs(&s.ast().attrs)?;
+    let container = ContainerAttrs::from_structure(&s)?;
+    if struct_alt.is_none() {
+        if let Some(repr) = ReprEnum::from_structure(&s)? {
+            return Ok(repr.visit_derive(&s));
+        }
+    }
     let mut body = TokenStream::default();
+    let mut bounds = Bounds::new(Derive::Visit, &container);
     for v in s.variants() {
         let mut counter = IdentCounter::default();
         let is_enum = v.prefix.is_some();
         let variant_ident = v.ast().ident;
+        let alt = VariantAlt::from_attrs(v.ast().attrs)?;
+        let (tag_binding, bindings) = split_tag(v, struct_alt.is_some() && alt.other)?;
+        let tag = match (&struct_alt, tag_binding) {
+            (Some(struct_alt), Some(bi)) => {
+                counter.next_key(&bi.ast().ident);
+                let tag = struct_alt.wire_tag(quote!(Clone::clone(#bi)));
+                Some(quote!(visitor.visit(&#tag)?;))
+            }
+            (Some(struct_alt), None) => {
+                let tag = alt.tag(variant_ident)?;
+                let tag = struct_alt.wire_tag(quote!(#tag));
+                Some(quote!(visitor.visit(&#tag)?;))
+            }
+            (None, _) => None,
+        };
 
         let pat = v.pat();
-        let keys = v
-            .bindings()
-            .iter()
-            .map(|bi| counter.next_key(&bi.ast().ident));
-        let bi = v.bindings().iter();
+        let mut fields = vec![];
+        for bi in bindings {
+            let attrs = container.field_attrs(bi.ast())?;
+            let key = attrs.key(&mut counter, bi.ast());
+            if attrs.is_skipped() {
+                continue;
+            }
+            bounds.add_field(bi.ast(), &attrs, attrs.with.is_none());
+            if let Some(element) = &attrs.recursive {
+                let ty = &bi.ast().ty;
+                let visit = quote! {
+                    <#ty as VisitRecursive<V, #element>>::visit_recursive(
+                        #bi,
+                        visitor,
+                        <#element as VisitWith<V>>::visit_with,
+                    )
+                };
+                fields.push(match key {
+                    Some(key) => quote!(visitor.with_key(#key.into(), |visitor| #visit)?;),
+                    None => quote!(#visit?;),
+                });
+                continue;
+            }
+            fields.push(match (attrs.with, key) {
+                (Some(with), Some(key)) => {
+                    quote!(visitor.with_key(#key.into(), |visitor| #with::visit(#bi, visitor))?;)
+                }
+                (Some(with), None) => quote!(#with::visit(#bi, visitor)?;),
+                (None, Some(key)) => quote!(visitor.visit_keyed(#key, #bi)?;),
+                (None, None) => quote!(visitor.visit(#bi)?;),
+            });
+        }
         let per_variant = quote!(
             {
-                #(visitor.visit_keyed(#keys, #bi)?;)*
+                #(#fields)*
                 //Ok::<_, <V as Visitor>::Error>(())
                 Ok(())
             }
@@ -46,6 +472,7 @@ fn visit_derive(s: synstructure::Structure) -> TokenStream {
         if is_enum {
             body.extend(quote! {
                 #pat => {
+                    #tag
                     visitor.with_key(stringify!(#variant_ident).into(), |visitor| #per_variant)
                 }
             });
@@ -58,242 +485,346 @@ fn visit_derive(s: synstructure::Structure) -> TokenStream {
         }
     }
 
-    let mut bounds = vec![];
-    for variant in s.variants() {
-        for binding in variant.bindings() {
-            let bounded_ty = binding.ast().ty.clone();
-            if !bounds.iter().any(|ty| ty == &bounded_ty) {
-                bounds.push(bounded_ty);
-            }
-        }
-    }
+    let alt_bound = struct_alt.as_ref().map(|alt| {
+        let ty = alt.wire_ty();
+        quote!(+ VisitType<#ty>)
+    });
+    let via = struct_alt.as_ref().and_then(StructAlt::via_from);
+    let Bounds {
+        types, predicates, ..
+    } = bounds;
 
-    s.gen_impl(quote! {
-        use represent::{VisitWith, VisitType, Visitor};
+    Ok(s.gen_impl(quote! {
+        use represent::{VisitRecursive, VisitWith, VisitType, Visitor};
         gen impl<V> VisitWith<V> for @Self
-            where V: Visitor #(+ VisitType<#bounds>)*
+            where V: Visitor #alt_bound #(+ VisitType<#types>)*, #(#predicates,)* #via
         {
             fn visit_with(&self, visitor: &mut V) -> Result<(), <V as Visitor>::Error> {
                 match self { #body }
             }
         }
-    })
+    }))
 }
-decl_derive!([VisitWith, attributes(alt)] => visit_derive);
+decl_derive!([VisitWith, attributes(alt, represent)] => visit_derive);
 
 fn make_with_derive(s: synstructure::Structure) -> TokenStream {
-    let alt = get_alt(&s.ast().attrs);
-    if !alt.is_empty() {
-        return make_with_derive_alt(s, alt);
-    }
-    assert_eq!(s.variants().len(), 1);
-
-    let structure = &s.variants()[0];
+    expand(try_make_with_derive(s))
+}
 
-    let mut bounds = vec![];
-    let mut idents = vec![];
-    for binding in structure.bindings() {
-        idents.push(&binding.ast().ident);
-        let bounded_ty = &binding.ast().ty;
-        if !bounds.iter().any(|ty| ty == &bounded_ty) {
-            bounds.push(bounded_ty);
-        }
+fn try_make_with_derive(mut s: synstructure::Structure) -> syn::Result<TokenStream> {
+    s.underscore_const(true);
+    let container = ContainerAttrs::from_structure(&s)?;
+    if let Some(alt) = StructAlt::from_attrs(&s.ast().attrs)? {
+        return make_with_derive_alt(s, alt, container);
+    }
+    if let Some(repr) = ReprEnum::from_structure(&s)? {
+        return Ok(repr.make_with_derive(&s));
+    }
+    if let syn::Data::Enum(_) = s.ast().data {
+        return Err(syn::Error::new_spanned(
+            &s.ast().ident,
+            "MakeWith on enums requires #[alt(ty = \"...\")] or a fieldless enum with an integer #[repr]",
+        ));
     }
 
-    let mut counter = IdentCounter::default();
-    let keys = idents.iter().map(|ident| counter.next_key(ident));
-
-    let ok = if is_tuple(structure.ast().fields) {
-        quote!(
-            Ok(Self (
-                #(#idents
-                    maker.make_keyed(#keys)?,
-                )*
-            ))
-        )
-    } else {
-        quote!(
-            Ok(Self {
-                #(#idents:
-                    maker.make_keyed(#keys)?,
-                )*
-            })
-        )
-    };
+    let mut bounds = Bounds::new(Derive::Make, &container);
+    let fields = make_fields(&s.variants()[0], &container, &mut bounds, None)?;
+    let ok = quote!(Ok(Self #fields));
+    let Bounds {
+        types, predicates, ..
+    } = bounds;
 
-    s.gen_impl(quote! {
-        use represent::{MakeWith, MakeType, Maker};
+    Ok(s.gen_impl(quote! {
+        use represent::{MakeRecursive, MakeWith, MakeType, Maker};
         gen impl<M> MakeWith<M> for @Self
-            where M: Maker #(+ MakeType<#bounds>)*
+            where M: Maker #(+ MakeType<#types>)*, #(#predicates,)*
         {
             fn make_with(maker: &mut M) -> Result<Self, <M as Maker>::Error> {
                 #ok
             }
         }
-    })
+    }))
 }
-decl_derive!([MakeWith, attributes(alt)] => make_with_derive);
+decl_derive!([MakeWith, attributes(alt, represent)] => make_with_derive);
 
 struct StructAlt {
     ty: Type,
+    /// type made and visited in place of `ty`, converted with `From`/`Into`
+    via: Option<Type>,
     default: Option<Expr>,
     err: Option<Type>,
     no_bounds: bool,
 }
 impl StructAlt {
-    fn new(vec: Vec<NestedMeta>) -> Self {
+    /// `None` if there are no `#[alt]` attributes
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Option<Self>> {
+        let Some(first) = attrs.iter().find(|attr| attr.path.is_ident("alt")) else {
+            return Ok(None);
+        };
         let mut ty = None;
+        let mut via = None;
         let mut default = None;
         let mut err = None;
         let mut no_bounds = false;
-        for item in vec {
+        for item in get_alt(attrs)? {
             match item {
                 NestedMeta::Meta(Meta::NameValue(MetaNameValue {
                     path,
                     lit: Lit::Str(value),
                     ..
                 })) => {
-                    let Some(ident) = path.get_ident() else {
-                        panic!("alt path {:?} is unsupported", path);
-                    };
-                    if ident == "ty" {
-                        ty = Some(value.parse().unwrap());
-                    } else if ident == "default" {
-                        default = Some(value.parse().unwrap());
-                    } else if ident == "err" {
-                        err = Some(value.parse().unwrap());
-                    } else if ident == "bounds" {
-                        no_bounds = &value.value() == "false";
+                    if path.is_ident("ty") {
+                        ty = Some(value.parse()?);
+                    } else if path.is_ident("via") {
+                        via = Some(value.parse()?);
+                    } else if path.is_ident("default") {
+                        default = Some(value.parse()?);
+                    } else if path.is_ident("err") {
+                        err = Some(value.parse()?);
+                    } else if path.is_ident("bounds") {
+                        if value.value() != "false" {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "`bounds` only accepts \"false\"",
+                            ));
+                        }
+                        no_bounds = true;
                     } else {
-                        panic!("alt ident {:?} is unsupported", ident);
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "unknown alt key, expected one of `ty`, `via`, `default`, `err`, `bounds`",
+                        ));
                     }
                 }
-                other => panic!("{:?} alt attr is unsupported", other),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "expected `key = \"value\"` in container alt attribute",
+                    ));
+                }
             }
         }
-        Self {
-            ty: ty.unwrap(),
+        let Some(ty) = ty else {
+            return Err(syn::Error::new_spanned(
+                first,
+                "alt attribute requires a tag type, e.g. #[alt(ty = \"u8\")]",
+            ));
+        };
+        Ok(Some(Self {
+            ty,
+            via,
             default,
             err,
             no_bounds,
+        }))
+    }
+
+    fn wire_ty(&self) -> &Type {
+        self.via.as_ref().unwrap_or(&self.ty)
+    }
+
+    /// `tag` expression converted to `wire_ty`
+    fn wire_tag(&self, tag: TokenStream) -> TokenStream {
+        let ty = &self.ty;
+        let tag = quote!({ let tag: #ty = #tag; tag });
+        match &self.via {
+            Some(via) => quote!(<#via as From<#ty>>::from(#tag)),
+            None => tag,
         }
     }
+
+    /// visit and analyze convert the tag into `via`
+    fn via_from(&self) -> Option<TokenStream> {
+        let ty = &self.ty;
+        self.via.as_ref().map(|via| quote!(#via: From<#ty>,))
+    }
+}
+
+fn get_alt(attrs: &[Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    get_nested(attrs, "alt")
 }
 
-fn get_alt(attrs: &[Attribute]) -> Vec<NestedMeta> {
-    attrs
-        .iter()
-        .filter(|attr| {
-            if let Some(ident) = attr.path.get_ident() {
-                ident == "alt"
-            } else {
-                false
+fn get_nested(attrs: &[Attribute], name: &str) -> syn::Result<Vec<NestedMeta>> {
+    let mut nested = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        match attr.parse_meta()? {
+            Meta::List(list) => nested.extend(list.nested),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    format!("expected a list, e.g. #[{}(...)]", name),
+                ));
             }
+        }
+    }
+    Ok(nested)
+}
+
+struct VariantAlt {
+    pattern: Option<Pat>,
+    tag: Option<Expr>,
+    /// catch-all for unknown tags, the first field keeps the tag
+    other: bool,
+}
+impl VariantAlt {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut pattern = None;
+        let mut tag = None;
+        let mut other = false;
+        for item in get_alt(attrs)? {
+            match item {
+                NestedMeta::Lit(Lit::Str(lit)) if pattern.is_none() && !other => {
+                    pattern = Some(lit.parse_with(parse_or_pat)?);
+                }
+                NestedMeta::Meta(Meta::Path(path))
+                    if path.is_ident("other") && pattern.is_none() && tag.is_none() =>
+                {
+                    other = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(value),
+                    ..
+                })) if path.is_ident("tag") && !other => {
+                    tag = Some(value.parse()?);
+                }
+                item => {
+                    return Err(syn::Error::new_spanned(
+                        item,
+                        "expected a pattern string, `tag = \"...\"` or a lone `other` in variant alt attribute",
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            pattern,
+            tag,
+            other,
         })
-        .map(|attr| attr.parse_meta().unwrap())
-        .flat_map(|meta| match meta {
-            Meta::List(list) => list.nested,
-            _ => unimplemented!(),
-        })
-        .collect()
+    }
+
+    /// tag written by visit, explicit `tag` or the pattern itself if it's a literal
+    fn tag(self, variant: &Ident) -> syn::Result<Expr> {
+        match (self.tag, self.pattern) {
+            (Some(tag), _) => Ok(tag),
+            (None, Some(Pat::Lit(lit))) => Ok(*lit.expr),
+            _ => Err(syn::Error::new_spanned(
+                variant,
+                "variant needs a literal alt pattern or #[alt(tag = \"...\")]",
+            )),
+        }
+    }
+}
+
+/// syn 1 doesn't parse top level `|` in patterns
+fn parse_or_pat(input: ParseStream) -> syn::Result<Pat> {
+    let mut cases = Punctuated::<Pat, Token![|]>::parse_separated_nonempty(input)?;
+    if cases.len() == 1 {
+        return Ok(cases.pop().unwrap().into_value());
+    }
+    Ok(Pat::Or(PatOr {
+        attrs: vec![],
+        leading_vert: None,
+        cases,
+    }))
 }
 
-fn get_alt_pattern(attrs: &[Attribute]) -> Option<Pat> {
-    match get_alt(attrs).first()? {
-        NestedMeta::Lit(Lit::Str(lit)) => lit.parse().ok(),
-        _ => None,
+/// the first binding holds the tag of an `#[alt(other)]` variant
+fn split_tag<'a, 'b>(
+    variant: &'a VariantInfo<'b>,
+    other: bool,
+) -> syn::Result<(Option<&'a BindingInfo<'b>>, &'a [BindingInfo<'b>])> {
+    match variant.bindings() {
+        [tag, rest @ ..] if other => Ok((Some(tag), rest)),
+        [] if other => Err(other_without_fields(variant)),
+        bindings => Ok((None, bindings)),
     }
 }
 
-fn is_tuple(fields: &Fields) -> bool {
-    matches!(fields, Fields::Unnamed(..))
+fn other_without_fields(variant: &VariantInfo) -> syn::Error {
+    syn::Error::new_spanned(
+        variant.ast().ident,
+        "`#[alt(other)]` variant needs a field to keep the tag",
+    )
 }
 
-fn make_with_derive_alt(st: synstructure::Structure, struct_alt: Vec<NestedMeta>) -> TokenStream {
+fn make_with_derive_alt(
+    st: synstructure::Structure,
+    struct_alt: StructAlt,
+    container: ContainerAttrs,
+) -> syn::Result<TokenStream> {
     let StructAlt {
         ty,
+        via,
         default,
         err,
         no_bounds,
-    } = StructAlt::new(struct_alt);
-    let mut bounds = vec![];
-    let mut variants = vec![];
-    struct Alt<'a> {
-        name: &'a Ident,
-        cond: Pat,
-        idents: Vec<&'a Option<Ident>>,
-        tuple: bool,
-    }
+    } = struct_alt;
+    let mut bounds = Bounds::new(Derive::Make, &container);
+    bounds.infer &= !no_bounds;
+    let mut conds = vec![];
+    let mut expr = vec![];
+    let mut other = None;
     for variant in st.variants() {
-        let Some(cond) = get_alt_pattern(variant.ast().attrs) else {
-            continue;
-        };
-
-        let mut idents = vec![];
-        for binding in variant.bindings() {
-            idents.push(&binding.ast().ident);
-            let bounded_ty = &binding.ast().ty;
-            if !no_bounds && !bounds.iter().any(|ty| ty == &bounded_ty) {
-                bounds.push(bounded_ty);
+        let alt = VariantAlt::from_attrs(variant.ast().attrs)?;
+        let name = variant.ast().ident;
+        if alt.other {
+            if other.is_some() || default.is_some() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "only one `#[alt(other)]` variant is allowed and it replaces `default`",
+                ));
             }
+            let fields = make_fields(variant, &container, &mut bounds, Some(quote!(alt)))?;
+            other = Some(quote!({
+                maker.with_key(stringify!(#name).into(), |maker| Ok(Self::#name #fields))?
+            }));
+            continue;
         }
-        variants.push(Alt {
-            name: variant.ast().ident,
-            cond,
-            idents,
-            tuple: is_tuple(variant.ast().fields),
-        });
-    }
-
-    let conds = variants.iter().map(|alt| &alt.cond);
-    let expr = variants.iter().map(|alt| {
-        let idents = alt.idents.as_slice();
-        let name = alt.name;
-        let mut counter = IdentCounter::default();
-        let keys = idents.iter().map(|ident| counter.next_key(ident));
-        let expr = if alt.idents.is_empty() {
-            quote!(Ok(Self::#name))
-        } else if alt.tuple {
-            let args = quote!(
-                #(#idents
-                    maker.make_keyed(#keys)?,
-                )*
-            );
-            quote!(
-                Ok(Self::#name(#args))
-            )
-        } else {
-            let args = quote!(
-                #(#idents:
-                    maker.make_keyed(#keys)?,
-                )*
-            );
-            quote!(
-                Ok(Self::#name{#args})
-            )
+        let Some(cond) = alt.pattern else {
+            return Err(syn::Error::new_spanned(
+                name,
+                "variant needs an alt pattern to be made, e.g. #[alt(\"1\")]",
+            ));
         };
-        quote!({
-            maker.with_key(stringify!(#name).into(), |maker| {#expr})?
-        })
-    });
-    let default = if let Some(default) = default {
-        quote!(_ => #default)
-    } else {
-        quote!()
+        let fields = make_fields(variant, &container, &mut bounds, None)?;
+        conds.push(cond);
+        expr.push(quote!({
+            maker.with_key(stringify!(#name).into(), |maker| Ok(Self::#name #fields))?
+        }));
+    }
+    let default = match (other, default) {
+        (Some(other), _) => quote!(_ => Ok(#other),),
+        (None, Some(default)) => quote!(_ => #default),
+        (None, None) => quote!(),
     };
     let err = if let Some(err) = err {
         quote!(#err: Into<M::Error>)
     } else {
         quote!()
     };
-    st.gen_impl(quote! {
-        use represent::{MakeWith, MakeType, Maker};
+    let (wire, via, make_alt) = match via {
+        Some(via) => (
+            via.clone(),
+            quote!(#via: Into<#ty>,),
+            quote!({
+                let tag: #via = MakeType::make_type(maker)?;
+                tag.into()
+            }),
+        ),
+        None => (ty.clone(), quote!(), quote!(MakeType::make_type(maker)?)),
+    };
+    let Bounds {
+        types, predicates, ..
+    } = bounds;
+    Ok(st.gen_impl(quote! {
+        use represent::{MakeRecursive, MakeWith, MakeType, Maker};
         gen impl<M> MakeWith<M> for @Self
-            where M: Maker + MakeType<#ty> #(+ MakeType<#bounds>)*,
-            #err
+            where M: Maker + MakeType<#wire> #(+ MakeType<#types>)*, #(#predicates,)*
+            #via #err
         {
             fn make_with(maker: &mut M) -> Result<Self, <M as Maker>::Error> {
-                let alt: #ty = MakeType::make_type(maker)?;
+                let alt: #ty = #make_alt;
                 match alt {
                     #(
                         #conds => Ok(#expr),
@@ -302,53 +833,258 @@ fn make_with_derive_alt(st: synstructure::Structure, struct_alt: Vec<NestedMeta>
                 }
             }
         }
-    })
+    }))
+}
+
+const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// fieldless enum with an integer `#[repr]` and no `#[alt]`, encoded as its discriminant
+struct ReprEnum {
+    ty: Ident,
+    variants: Vec<Ident>,
+}
+
+impl ReprEnum {
+    /// `None` unless every variant is a unit and the repr is an integer
+    fn from_structure(s: &synstructure::Structure) -> syn::Result<Option<Self>> {
+        let syn::Data::Enum(data) = &s.ast().data else {
+            return Ok(None);
+        };
+        if data
+            .variants
+            .iter()
+            .any(|v| !matches!(v.fields, Fields::Unit))
+        {
+            return Ok(None);
+        }
+        let attrs = &s.ast().attrs;
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+            let Meta::List(list) = attr.parse_meta()? else {
+                continue;
+            };
+            for item in list.nested {
+                let NestedMeta::Meta(Meta::Path(path)) = item else {
+                    continue;
+                };
+                match path.get_ident() {
+                    Some(ty) if INT_REPRS.contains(&ty.to_string().as_str()) => {
+                        return Ok(Some(Self {
+                            ty: ty.clone(),
+                            variants: data.variants.iter().map(|v| v.ident.clone()).collect(),
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// discriminant of `self` as the repr type
+    fn tag(&self) -> TokenStream {
+        let Self { ty, variants } = self;
+        quote!(match self { #(Self::#variants => Self::#variants as #ty,)* })
+    }
+
+    fn make_with_derive(&self, s: &synstructure::Structure) -> TokenStream {
+        let Self { ty, variants } = self;
+        s.gen_impl(quote! {
+            use represent::{MakeWith, MakeType, Maker, UnknownDiscriminant};
+            gen impl<M> MakeWith<M> for @Self
+                where M: Maker + MakeType<#ty>, UnknownDiscriminant: Into<M::Error>
+            {
+                fn make_with(maker: &mut M) -> Result<Self, <M as Maker>::Error> {
+                    let tag: #ty = MakeType::make_type(maker)?;
+                    #(
+                        if tag == Self::#variants as #ty {
+                            return Ok(Self::#variants);
+                        }
+                    )*
+                    Err(UnknownDiscriminant {
+                        value: tag as i128,
+                        type_name: std::any::type_name::<Self>(),
+                    }
+                    .into())
+                }
+            }
+        })
+    }
+
+    fn visit_derive(&self, s: &synstructure::Structure) -> TokenStream {
+        let ty = &self.ty;
+        let tag = self.tag();
+        s.gen_impl(quote! {
+            use represent::{VisitWith, VisitType, Visitor};
+            gen impl<V> VisitWith<V> for @Self
+                where V: Visitor + VisitType<#ty>
+            {
+                fn visit_with(&self, visitor: &mut V) -> Result<(), <V as Visitor>::Error> {
+                    let tag: #ty = #tag;
+                    visitor.visit(&tag)
+                }
+            }
+        })
+    }
+
+    fn analyze_derive(&self, s: &synstructure::Structure) -> TokenStream {
+        let ty = &self.ty;
+        let tag = self.tag();
+        s.gen_impl(quote! {
+            use represent::{AnalyzeWith, AnalyzeType, SizeOverflow, TypeAnalyzer, TypeSize};
+            gen impl<A> AnalyzeWith<A> for @Self
+                where A: TypeAnalyzer + AnalyzeType<#ty>
+            {
+                const CONST_SIZE: TypeSize = <A as AnalyzeType<#ty>>::TYPE_CONST_SIZE;
+
+                fn fixed_size(analyzer: &A) -> usize {
+                    <A as AnalyzeType<#ty>>::type_fixed_size(analyzer)
+                }
+
+                represent::expect_sizes!(dynamic A);
+
+                fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+                    <A as AnalyzeType<#ty>>::type_try_fixed_size(analyzer)
+                }
+
+                fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+                    let tag: #ty = #tag;
+                    analyzer.type_try_dynamic_size(&tag)
+                }
+            }
+        })
+    }
 }
 
 fn analyze_derive(s: synstructure::Structure) -> TokenStream {
-    let structure = &s.variants()[0];
+    expand(try_analyze_derive(s))
+}
+
+fn try_analyze_derive(mut s: synstructure::Structure) -> syn::Result<TokenStream> {
+    s.underscore_const(true);
+    let struct_alt = StructAlt::from_attrs(&s.ast().attrs)?;
+    let container = ContainerAttrs::from_structure(&s)?;
+    if struct_alt.is_none() {
+        if let Some(repr) = ReprEnum::from_structure(&s)? {
+            return Ok(repr.analyze_derive(&s));
+        }
+    }
+    let is_enum = matches!(s.ast().data, syn::Data::Enum(_));
 
-    let mut bounds = vec![];
-    let mut types = vec![];
-    let mut idents = vec![];
-    for binding in structure.bindings() {
-        types.push(&binding.ast().ty);
-        idents.push(&binding.ast().ident);
-        let bounded_ty = &binding.ast().ty;
-        if !bounds.iter().any(|ty| *ty == bounded_ty) {
-            bounds.push(bounded_ty);
+    let mut bounds = Bounds::new(Derive::Analyze, &container);
+    let mut variant_sizes = vec![];
+    let mut fixed_sizes = vec![];
+    let mut body = TokenStream::default();
+    for v in s.variants() {
+        let alt = VariantAlt::from_attrs(v.ast().attrs)?;
+        let (tag_binding, bindings) = split_tag(v, struct_alt.is_some() && alt.other)?;
+        let mut const_sizes = vec![];
+        let mut dynamic_sizes = vec![];
+        for bi in bindings {
+            let attrs = container.field_attrs(bi.ast())?;
+            if attrs.is_skipped() {
+                continue;
+            }
+            bounds.add_field(bi.ast(), &attrs, attrs.with.is_none());
+            let ty = &bi.ast().ty;
+            if let Some(with) = attrs.with {
+                const_sizes.push(quote!(TypeSize::Dynamic));
+                // spanned on the attribute, so a module without `size` is reported there
+                let size = quote_spanned! {with.span()=>
+                    let size: fn(&#ty, &A) -> Result<usize, SizeOverflow> = #with::size;
+                };
+                dynamic_sizes.push(quote!({ #size size(#bi, analyzer) }));
+                continue;
+            }
+            if let Some(element) = &attrs.recursive {
+                const_sizes.push(quote!(TypeSize::Dynamic));
+                dynamic_sizes.push(quote! {
+                    <#ty as AnalyzeRecursive<A, #element>>::try_recursive_size(
+                        #bi,
+                        analyzer,
+                        <#element as AnalyzeWith<A>>::try_dynamic_size,
+                    )
+                });
+                continue;
+            }
+            const_sizes.push(quote!(<A as AnalyzeType<#ty>>::TYPE_CONST_SIZE));
+            fixed_sizes.push(quote!(<A as AnalyzeType<#ty>>::type_try_fixed_size(analyzer)));
+            dynamic_sizes.push(quote!(analyzer.type_try_dynamic_size(#bi)));
         }
+        variant_sizes.push(quote!(sum_sizes([#(#const_sizes),*])));
+
+        let tag = match (&struct_alt, tag_binding) {
+            (Some(struct_alt), Some(bi)) => {
+                let tag = struct_alt.wire_tag(quote!(Clone::clone(#bi)));
+                Some(quote!(analyzer.type_try_dynamic_size(&#tag),))
+            }
+            (Some(struct_alt), None) => {
+                let tag = alt.tag(v.ast().ident)?;
+                let tag = struct_alt.wire_tag(quote!(#tag));
+                Some(quote!(analyzer.type_try_dynamic_size(&#tag),))
+            }
+            (None, _) => None,
+        };
+        let pat = v.pat();
+        body.extend(quote! {
+            #pat => checked_sum([
+                Ok(0usize),
+                #tag
+                #(#dynamic_sizes),*
+            ]),
+        });
     }
 
-    s.gen_impl(quote! {
-        use represent::{AnalyzeWith, AnalyzeType, TypeAnalyzer, TypeSize, sum_sizes};
+    let alt_ty = struct_alt.as_ref().map(StructAlt::wire_ty);
+    let alt_bound = alt_ty.map(|ty| quote!(+ AnalyzeType<#ty>));
+    let via = struct_alt.as_ref().and_then(StructAlt::via_from);
+    let Bounds {
+        types, predicates, ..
+    } = bounds;
+    let const_size = if is_enum {
+        let tag = alt_ty.map(|ty| quote!(<A as AnalyzeType<#ty>>::TYPE_CONST_SIZE,));
+        quote!(sum_sizes([#tag same_sizes([#(#variant_sizes),*])]))
+    } else {
+        quote!(#(#variant_sizes)*)
+    };
+    let fixed = if is_enum {
+        quote!()
+    } else {
+        quote! {
+            represent::expect_sizes!(fixed A);
+
+            fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+                checked_sum([
+                    Ok(0usize),
+                    #(#fixed_sizes),*
+                ])
+            }
+        }
+    };
+
+    Ok(s.gen_impl(quote! {
+        use represent::{
+            AnalyzeRecursive, AnalyzeWith, AnalyzeType, SizeOverflow, TypeAnalyzer, TypeSize,
+            checked_sum, same_sizes, sum_sizes,
+        };
         gen impl<A> AnalyzeWith<A> for @Self
         where
-            A: TypeAnalyzer #(+ AnalyzeType<#bounds>)*
+            A: TypeAnalyzer #alt_bound #(+ AnalyzeType<#types>)*, #(#predicates,)* #via
         {
-            const CONST_SIZE: TypeSize = sum_sizes([
-                    #(<A as AnalyzeType<#types>>::TYPE_CONST_SIZE),*
-            ]);
+            const CONST_SIZE: TypeSize = #const_size;
 
-            fn fixed_size(analyzer: &A) -> usize {
-                let arr = [
-                    0usize,
-                    #(<A as AnalyzeType<#types>>::type_fixed_size(analyzer)),*
-                ];
-                std::array::IntoIter::new(arr).sum()
-            }
+            #fixed
+
+            represent::expect_sizes!(dynamic A);
 
-            fn dynamic_size(&self, analyzer: &A) -> usize {
-                let arr = [
-                    0usize,
-                    #(analyzer.type_dynamic_size(&self.#idents)),*
-                ];
-                std::array::IntoIter::new(arr).sum()
+            fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+                match self { #body }
             }
         }
-    })
+    }))
 }
-decl_derive!([AnalyzeWith] => analyze_derive);
+decl_derive!([AnalyzeWith, attributes(alt, represent)] => analyze_derive);
 
 #[cfg(test)]
 mod test {
@@ -369,7 +1105,7 @@ mod test {
             }
             expands to {
                 #[allow(non_upper_case_globals)]
-                const _DERIVE_Visit_V_FOR_A: () = {
+                const _: () = {
                     use represent::{VisitWith, VisitType, Visitor};
                     impl<V> VisitWith<V> for A
                     where
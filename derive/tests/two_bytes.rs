@@ -1,5 +1,10 @@
-use represent::{MakeType, MakeWith, Maker, VisitType, VisitWith, Visitor};
-use represent_derive::{MakeWith, VisitWith};
+use std::convert::TryFrom;
+
+use represent::{
+    AnalyzeStr, AnalyzeType, AnalyzeWith, MakeStr, MakeType, MakeWith, Maker, RepresentKey,
+    SizeOverflow, TypeAnalyzer, TypeSize, VisitStr, VisitType, VisitWith, Visitor,
+};
+use represent_derive::{AnalyzeWith, MakeWith, VisitWith};
 
 #[derive(Debug)]
 struct Bytes {
@@ -43,13 +48,39 @@ impl<T: MakeWith<Bytes>> MakeType<T> for Bytes {
     }
 }
 
-#[derive(Debug, VisitWith, MakeWith, PartialEq)]
+impl TypeAnalyzer for Bytes {}
+
+impl AnalyzeWith<Bytes> for u8 {
+    const CONST_SIZE: TypeSize = TypeSize::Const(1);
+}
+
+impl<T: AnalyzeWith<Bytes>> AnalyzeType<T> for Bytes {
+    const TYPE_CONST_SIZE: TypeSize = T::CONST_SIZE;
+
+    fn type_fixed_size(&self) -> usize {
+        T::fixed_size(self)
+    }
+
+    fn type_dynamic_size(&self, target: &T) -> usize {
+        target.dynamic_size(self)
+    }
+
+    fn type_try_fixed_size(&self) -> Result<usize, SizeOverflow> {
+        T::try_fixed_size(self)
+    }
+
+    fn type_try_dynamic_size(&self, target: &T) -> Result<usize, SizeOverflow> {
+        target.try_dynamic_size(self)
+    }
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
 struct TwoBytes {
     first: u8,
     second: u8,
 }
 
-#[derive(Debug, MakeWith, PartialEq, VisitWith)]
+#[derive(Debug, MakeWith, PartialEq, VisitWith, AnalyzeWith)]
 #[alt(ty = "u8", err = "()", default = "Err(().into())")]
 enum SumType {
     #[alt("1")]
@@ -96,3 +127,506 @@ fn make_sum_type() {
     st([2], Err(()));
     st([2, 3], Err(()));
 }
+
+#[test]
+fn visit_sum_type() {
+    for (value, expected) in [
+        (SumType::One(2, 3), &[1, 2, 3][..]),
+        (SumType::Two { _foo: 3, _bar: 4 }, &[2, 3, 4]),
+        (SumType::Three, &[3]),
+    ] {
+        let mut bytes = Bytes { buf: vec![] };
+        assert_eq!(bytes.type_dynamic_size(&value), expected.len());
+        bytes.visit(&value).unwrap();
+        assert_eq!(&bytes.buf, expected);
+
+        let new_value: SumType = bytes.make().unwrap();
+        assert_eq!(&value, &new_value);
+    }
+}
+
+#[derive(Debug, MakeWith, PartialEq, VisitWith, AnalyzeWith)]
+#[alt(ty = "u8", err = "()", default = "Err(().into())")]
+enum SharedTag {
+    #[alt("1")]
+    One(u8),
+    #[alt("4 | 5", tag = "5")]
+    Five(u8),
+}
+
+#[test]
+fn alt_with_several_tags() {
+    for (slice, res) in [
+        (&[4, 1][..], Ok(SharedTag::Five(1))),
+        (&[5, 1], Ok(SharedTag::Five(1))),
+        (&[1, 2], Ok(SharedTag::One(2))),
+        (&[6, 1], Err(())),
+    ] {
+        let mut bytes = Bytes { buf: slice.into() };
+        assert_eq!(bytes.make(), res);
+    }
+
+    let mut bytes = Bytes { buf: vec![] };
+    bytes.visit(&SharedTag::Five(1)).unwrap();
+    assert_eq!(&bytes.buf, &[5, 1]);
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+struct Pair(u8, TwoBytes);
+
+#[derive(Debug, AnalyzeWith)]
+#[alt(ty = "u8")]
+enum SameSize {
+    #[alt("1")]
+    A(u8, u8),
+    #[alt("2")]
+    B { _first: u8, _second: u8 },
+}
+
+#[test]
+fn analyze_sizes() {
+    assert!(matches!(
+        <Bytes as AnalyzeType<Pair>>::TYPE_CONST_SIZE,
+        TypeSize::Const(3)
+    ));
+    let bytes = Bytes { buf: vec![] };
+    let pair = Pair(
+        1,
+        TwoBytes {
+            first: 2,
+            second: 3,
+        },
+    );
+    assert_eq!(bytes.type_dynamic_size(&pair), 3);
+
+    assert!(matches!(
+        <Bytes as AnalyzeType<SameSize>>::TYPE_CONST_SIZE,
+        TypeSize::Const(3)
+    ));
+    assert_eq!(bytes.type_dynamic_size(&SameSize::A(1, 2)), 3);
+    let value = SameSize::B {
+        _first: 1,
+        _second: 2,
+    };
+    assert_eq!(bytes.type_dynamic_size(&value), 3);
+    assert!(matches!(
+        <Bytes as AnalyzeType<SumType>>::TYPE_CONST_SIZE,
+        TypeSize::Dynamic
+    ));
+}
+
+mod doubled {
+    use represent::{MakeType, SizeOverflow, TypeAnalyzer, VisitType};
+
+    pub fn make<M: MakeType<u8>>(maker: &mut M) -> Result<u8, M::Error> {
+        let value: u8 = maker.make()?;
+        Ok(value / 2)
+    }
+
+    pub fn visit<V: VisitType<u8>>(value: &u8, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit(&(value * 2))
+    }
+
+    pub fn size<A: TypeAnalyzer>(_value: &u8, _analyzer: &A) -> Result<usize, SizeOverflow> {
+        Ok(1)
+    }
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+struct FieldAttrs {
+    first: u8,
+    #[represent(skip)]
+    cache: Vec<String>,
+    #[represent(default = "7")]
+    version: u8,
+    #[represent(with = "doubled")]
+    half: u8,
+}
+
+#[test]
+fn field_attrs() {
+    let mut bytes = Bytes { buf: vec![] };
+    let value = FieldAttrs {
+        first: 1,
+        cache: vec!["cached".into()],
+        version: 3,
+        half: 5,
+    };
+    assert_eq!(bytes.type_dynamic_size(&value), 2);
+    assert!(matches!(
+        <Bytes as AnalyzeType<FieldAttrs>>::TYPE_CONST_SIZE,
+        TypeSize::Dynamic
+    ));
+
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[1, 10]);
+
+    let new_value: FieldAttrs = bytes.make().unwrap();
+    let expected = FieldAttrs {
+        first: 1,
+        cache: vec![],
+        version: 7,
+        half: 5,
+    };
+    assert_eq!(&new_value, &expected);
+}
+
+/// Records the key path of every visited byte.
+#[derive(Default)]
+struct Trace {
+    path: Vec<String>,
+    seen: Vec<String>,
+}
+
+impl Visitor for Trace {
+    type Error = ();
+
+    fn with_key(
+        &mut self,
+        key: RepresentKey,
+        func: impl FnOnce(&mut Self) -> Result<(), ()>,
+    ) -> Result<(), ()> {
+        self.path.push(key.to_string());
+        let res = func(self);
+        self.path.pop();
+        res
+    }
+}
+
+impl VisitType<u8> for Trace {
+    fn visit(&mut self, _target: &u8) -> Result<(), ()> {
+        self.seen.push(self.path.join("."));
+        Ok(())
+    }
+}
+
+impl<T: VisitWith<Self>> VisitType<T> for Trace {
+    fn visit(&mut self, target: &T) -> Result<(), ()> {
+        target.visit_with(self)
+    }
+}
+
+#[derive(Debug, VisitWith, MakeWith, PartialEq)]
+#[represent(transparent)]
+struct Wrapper(u8);
+
+#[derive(Debug, VisitWith, MakeWith, PartialEq)]
+struct Keys {
+    #[represent(key = "msgId")]
+    id: u8,
+    #[represent(flatten)]
+    pair: TwoBytes,
+    nested: TwoBytes,
+    wrapped: Wrapper,
+}
+
+#[test]
+fn keys_and_flatten() {
+    let value = Keys {
+        id: 1,
+        pair: TwoBytes {
+            first: 2,
+            second: 3,
+        },
+        nested: TwoBytes {
+            first: 4,
+            second: 5,
+        },
+        wrapped: Wrapper(6),
+    };
+    let mut trace = Trace::default();
+    trace.visit(&value).unwrap();
+    assert_eq!(
+        trace.seen,
+        [
+            "msgId",
+            "first",
+            "second",
+            "nested.first",
+            "nested.second",
+            "wrapped"
+        ]
+    );
+
+    let mut bytes = Bytes { buf: vec![] };
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[1, 2, 3, 4, 5, 6]);
+    let new_value: Keys = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+struct ArrayAndTuple {
+    arr: [u8; 3],
+    pair: (u8, TwoBytes),
+}
+
+#[test]
+fn array_and_tuple() {
+    let mut bytes = Bytes { buf: vec![] };
+    let value = ArrayAndTuple {
+        arr: [1, 2, 3],
+        pair: (
+            4,
+            TwoBytes {
+                first: 5,
+                second: 6,
+            },
+        ),
+    };
+
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[1, 2, 3, 4, 5, 6]);
+
+    let new_value: ArrayAndTuple = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+}
+
+#[test]
+fn array_and_tuple_size() {
+    assert_eq!(
+        <Bytes as AnalyzeType<[u8; 3]>>::TYPE_CONST_SIZE,
+        TypeSize::Const(3)
+    );
+    assert_eq!(
+        <Bytes as AnalyzeType<ArrayAndTuple>>::TYPE_CONST_SIZE,
+        TypeSize::Const(6)
+    );
+    let bytes = Bytes { buf: vec![] };
+    assert_eq!(ArrayAndTuple::try_fixed_size(&bytes), Ok(6));
+}
+
+#[derive(Debug, VisitWith, MakeWith, PartialEq)]
+struct Pointers {
+    boxed: Box<TwoBytes>,
+    shared: std::rc::Rc<u8>,
+    cow: std::borrow::Cow<'static, [u8; 2]>,
+}
+
+#[test]
+fn pointers() {
+    let mut bytes = Bytes { buf: vec![] };
+    let value = Pointers {
+        boxed: Box::new(TwoBytes {
+            first: 1,
+            second: 2,
+        }),
+        shared: std::rc::Rc::new(3),
+        cow: std::borrow::Cow::Borrowed(&[4, 5]),
+    };
+
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[1, 2, 3, 4, 5]);
+
+    let new_value: Pointers = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+}
+
+impl MakeStr for Bytes {
+    fn make_str(&mut self) -> Result<String, ()> {
+        let len: u8 = self.make()?;
+        let bytes = (0..len).map(|_| self.make()).collect::<Result<_, _>>()?;
+        String::from_utf8(bytes).map_err(drop)
+    }
+}
+
+impl VisitStr for Bytes {
+    fn visit_str(&mut self, target: &str) -> Result<(), ()> {
+        let len = u8::try_from(target.len()).map_err(drop)?;
+        self.visit(&len)?;
+        target.bytes().try_for_each(|byte| self.visit(&byte))
+    }
+}
+
+impl AnalyzeStr for Bytes {
+    fn try_str_size(&self, target: &str) -> Result<usize, SizeOverflow> {
+        target.len().checked_add(1).ok_or(SizeOverflow)
+    }
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+struct Names {
+    short: std::borrow::Cow<'static, str>,
+    long: String,
+}
+
+#[test]
+fn strings() {
+    let mut bytes = Bytes { buf: vec![] };
+    let value = Names {
+        short: std::borrow::Cow::Borrowed("ab"),
+        long: "cde".to_string(),
+    };
+
+    assert_eq!(value.try_dynamic_size(&bytes), Ok(7));
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[2, b'a', b'b', 3, b'c', b'd', b'e']);
+
+    let new_value: Names = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+#[represent(bound(
+    make = "M: MakeType<Box<T>>",
+    visit = "V: VisitType<Box<T>>",
+    analyze = "A: AnalyzeType<Box<T>>"
+))]
+struct Boxed<T> {
+    inner: Box<T>,
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+struct OnlyWith {
+    #[represent(
+        with = "doubled",
+        bound(make = "M: MakeType<u8>", visit = "V: VisitType<u8>")
+    )]
+    half: u8,
+}
+
+#[test]
+fn bound_attrs() {
+    let mut bytes = Bytes { buf: vec![] };
+    let value = Boxed {
+        inner: Box::new(OnlyWith { half: 3 }),
+    };
+    assert_eq!(bytes.type_dynamic_size(&value), 1);
+
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[6]);
+
+    let new_value: Boxed<OnlyWith> = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+#[alt(ty = "u8", err = "()", default = "Err(().into())")]
+enum Expr {
+    #[alt("0")]
+    Leaf(u8),
+    #[alt("1")]
+    Neg(#[represent(recursive)] Box<Expr>),
+    #[alt("2")]
+    Add(
+        #[represent(recursive)] Box<Expr>,
+        #[represent(recursive)] Box<Expr>,
+    ),
+}
+
+/// `cycle` keeps `Tree` from requiring `Forest`, which already requires `Tree`.
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+#[alt(ty = "u8", err = "()", default = "Err(().into())")]
+enum Tree {
+    #[alt("0")]
+    Leaf(u8),
+    #[alt("1")]
+    Branch(#[represent(recursive = "Forest", cycle)] Box<Forest>),
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+struct Forest {
+    #[represent(recursive = "Tree")]
+    first: Box<Tree>,
+    #[represent(recursive = "Tree")]
+    second: Box<Tree>,
+}
+
+#[test]
+fn recursive() {
+    let mut bytes = Bytes { buf: vec![] };
+    let value = Expr::Add(
+        Box::new(Expr::Neg(Box::new(Expr::Leaf(3)))),
+        Box::new(Expr::Leaf(4)),
+    );
+    assert!(matches!(
+        <Bytes as AnalyzeType<Expr>>::TYPE_CONST_SIZE,
+        TypeSize::Dynamic
+    ));
+    assert_eq!(bytes.type_dynamic_size(&value), 6);
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[2, 1, 0, 3, 0, 4]);
+    let new_value: Expr = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+
+    let value = Tree::Branch(Box::new(Forest {
+        first: Box::new(Tree::Leaf(1)),
+        second: Box::new(Tree::Branch(Box::new(Forest {
+            first: Box::new(Tree::Leaf(2)),
+            second: Box::new(Tree::Leaf(3)),
+        }))),
+    }));
+    assert_eq!(bytes.type_dynamic_size(&value), 8);
+    bytes.visit(&value).unwrap();
+    assert_eq!(&bytes.buf, &[1, 0, 1, 1, 0, 2, 0, 3]);
+    let new_value: Tree = bytes.make().unwrap();
+    assert_eq!(&value, &new_value);
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+#[alt(ty = "u8")]
+enum Forward {
+    #[alt("1")]
+    Known(u8),
+    #[alt(other)]
+    Unknown { tag: u8, body: u8 },
+}
+
+#[test]
+fn alt_other() {
+    for (value, expected) in [
+        (Forward::Known(2), [1, 2]),
+        (Forward::Unknown { tag: 7, body: 9 }, [7, 9]),
+    ] {
+        let mut bytes = Bytes {
+            buf: expected.into(),
+        };
+        let new_value: Forward = bytes.make().unwrap();
+        assert_eq!(&new_value, &value);
+        assert_eq!(bytes.type_dynamic_size(&value), 2);
+        bytes.visit(&value).unwrap();
+        assert_eq!(&bytes.buf, &expected);
+    }
+}
+
+/// Tag written with the high bit set.
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith)]
+struct Flagged(u8);
+
+impl From<u8> for Flagged {
+    fn from(tag: u8) -> Self {
+        Flagged(tag | 0x80)
+    }
+}
+
+impl From<Flagged> for u8 {
+    fn from(tag: Flagged) -> Self {
+        tag.0 & 0x7f
+    }
+}
+
+#[derive(Debug, VisitWith, MakeWith, AnalyzeWith, PartialEq)]
+#[alt(ty = "u8", via = "Flagged")]
+enum ViaTag {
+    #[alt("1")]
+    One(u8),
+    #[alt(other)]
+    Other(u8),
+}
+
+#[test]
+fn alt_via() {
+    for (value, expected) in [
+        (ViaTag::One(5), &[0x81, 5][..]),
+        (ViaTag::Other(3), &[0x83]),
+    ] {
+        let mut bytes = Bytes {
+            buf: expected.to_vec(),
+        };
+        let new_value: ViaTag = bytes.make().unwrap();
+        assert_eq!(&new_value, &value);
+        assert_eq!(bytes.type_dynamic_size(&value), expected.len());
+        bytes.visit(&value).unwrap();
+        assert_eq!(&bytes.buf, &expected);
+    }
+}
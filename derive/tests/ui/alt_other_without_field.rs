@@ -0,0 +1,12 @@
+use represent_derive::MakeWith;
+
+#[derive(MakeWith)]
+#[alt(ty = "u8")]
+enum Message {
+    #[alt("1")]
+    Ping,
+    #[alt(other)]
+    Unknown,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use represent_derive::MakeWith;
+
+#[derive(MakeWith)]
+enum Message {
+    Ping,
+    Pong,
+}
+
+fn main() {}
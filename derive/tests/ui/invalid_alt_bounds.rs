@@ -0,0 +1,10 @@
+use represent_derive::MakeWith;
+
+#[derive(MakeWith)]
+#[alt(ty = "u8", bounds = "flase")]
+enum Message {
+    #[alt("1")]
+    Ping,
+}
+
+fn main() {}
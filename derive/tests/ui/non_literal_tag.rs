@@ -0,0 +1,10 @@
+use represent_derive::VisitWith;
+
+#[derive(VisitWith)]
+#[alt(ty = "u8")]
+enum Message {
+    #[alt("1..=3")]
+    Ping,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use represent_derive::MakeWith;
+
+#[derive(MakeWith)]
+#[represent(transparent)]
+struct Pair(u8, u8);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use represent_derive::MakeWith;
+
+#[derive(MakeWith)]
+#[represent(bound(serialize = "M: Clone"))]
+struct Header {
+    id: u8,
+}
+
+fn main() {}
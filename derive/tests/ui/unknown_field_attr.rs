@@ -0,0 +1,9 @@
+use represent_derive::VisitWith;
+
+#[derive(VisitWith)]
+struct Message {
+    #[represent(skipp)]
+    id: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use represent_derive::AnalyzeWith;
+
+mod halved {}
+
+#[derive(AnalyzeWith)]
+struct Message {
+    #[represent(with = "halved")]
+    id: u8,
+}
+
+fn main() {}
@@ -0,0 +1,87 @@
+pub trait Endian {
+    const IS_NATIVE: bool;
+
+    /// Converts elements between wire and host byte order in place.
+    fn swap_to_native<T: Scalar>(blob: &mut [T]) {
+        if !Self::IS_NATIVE {
+            swap_elements(blob);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LittleEndian;
+
+impl Endian for LittleEndian {
+    const IS_NATIVE: bool = cfg!(target_endian = "little");
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BigEndian;
+
+impl Endian for BigEndian {
+    const IS_NATIVE: bool = cfg!(target_endian = "big");
+}
+
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+#[cfg(target_endian = "little")]
+pub type SwappedEndian = BigEndian;
+#[cfg(target_endian = "big")]
+pub type SwappedEndian = LittleEndian;
+
+/// Pod types that are byte-swapped as a whole.
+pub trait Scalar: bytemuck::Pod {
+    #[doc(hidden)]
+    // to prevent manual implementations
+    fn _sealead(sealed: private::Sealed);
+}
+
+macro_rules! impl_scalar {
+    ($($ty:ty),*) => {$(
+        impl Scalar for $ty {
+            #[doc(hidden)]
+            fn _sealead(sealed: private::Sealed) {
+                match sealed {}
+            }
+        }
+    )*};
+}
+
+impl_scalar!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// Element of a blob in `E` byte order: any Pod type is copied as is in native byte order,
+/// swapped blobs only take [`Scalar`]s and byte arrays.
+pub trait BlobElement<E>: bytemuck::Pod {
+    fn swap_to_native(blob: &mut [Self]);
+}
+
+impl<T: bytemuck::Pod> BlobElement<NativeEndian> for T {
+    fn swap_to_native(_blob: &mut [Self]) {}
+}
+
+impl<T: Scalar> BlobElement<SwappedEndian> for T {
+    fn swap_to_native(blob: &mut [Self]) {
+        swap_elements(blob);
+    }
+}
+
+impl<const N: usize> BlobElement<SwappedEndian> for [u8; N] {
+    fn swap_to_native(_blob: &mut [Self]) {}
+}
+
+fn swap_elements<T: Scalar>(blob: &mut [T]) {
+    let size = std::mem::size_of::<T>();
+    if size > 1 {
+        bytemuck::cast_slice_mut::<T, u8>(blob)
+            .chunks_exact_mut(size)
+            .for_each(<[u8]>::reverse);
+    }
+}
+
+mod private {
+    pub enum Sealed {}
+}
@@ -1,11 +1,22 @@
-use std::{convert::TryInto, default, fmt, marker::PhantomData};
+use std::{borrow::Cow, convert::TryInto, fmt, marker::PhantomData, str::FromStr};
 
 use represent::{
-    AnalyzeType, AnalyzeWith, MakeType, MakeWith, TypeAnalyzer, TypeSize, VisitType, VisitWith,
+    AnalyzeRecursive, AnalyzeType, AnalyzeWith, MakeRecursive, MakeType, MakeWith, SizeOverflow,
+    TypeAnalyzer, TypeSize, VisitRecursive, VisitType, VisitWith, Visitor, checked_repeat_size,
+    checked_sum, const_repeat_size,
 };
 
-use super::length::{Length, LengthError};
-use crate::traits::{MakeBlob, VisitBlob};
+use super::{
+    MaybeHas,
+    collections::repeat_size_and_len,
+    encoding::{Encoding, EncodingError, Utf8},
+    length::{Length, LengthError},
+    limits::{self, DecodeLimits, LimitError},
+};
+use crate::{
+    endian::BlobElement,
+    traits::{MakeBlob, VisitBlob},
+};
 
 // region: BigArr
 pub struct BigArr<T, LEN>(pub Vec<T>, pub(crate) PhantomData<LEN>);
@@ -63,42 +74,48 @@ impl<D: TypeAnalyzer + AnalyzeType<LEN> + AnalyzeType<Length<LEN>>, T, LEN> Anal
         <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE,
     );
 
-    fn fixed_size(analyzer: &D) -> usize {
-        let reps = <D as AnalyzeType<Length<LEN>>>::type_fixed_size(analyzer);
+    represent::expect_sizes!(D);
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        let reps = <D as AnalyzeType<Length<LEN>>>::type_try_fixed_size(analyzer)?;
         let header = <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE.expect_const();
-        header + reps * std::mem::size_of::<T>()
+        checked_repeat_size(header, reps, std::mem::size_of::<T>())
     }
 
-    fn dynamic_size(&self, _analyzer: &D) -> usize {
+    fn try_dynamic_size(&self, _analyzer: &D) -> Result<usize, SizeOverflow> {
         let reps = self.0.len();
         let header = <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE.expect_const();
-        header + reps * std::mem::size_of::<T>()
+        checked_repeat_size(header, reps, std::mem::size_of::<T>())
     }
 }
 
 const fn size_and_len(bytes: usize, reps: TypeSize, header: TypeSize) -> TypeSize {
     let header = header.expect_const();
     match reps {
-        TypeSize::Const(size) => TypeSize::Const(header + bytes * size),
+        TypeSize::Const(size) => TypeSize::Const(const_repeat_size(header, size, bytes)),
         _ => reps,
     }
 }
 
-impl<M, T: bytemuck::Pod, LEN> MakeWith<M> for BigArr<T, LEN>
+impl<M, T, LEN> MakeWith<M> for BigArr<T, LEN>
 where
-    M: MakeType<LEN> + AnalyzeType<Length<LEN>> + MakeBlob,
+    M: MakeType<LEN> + AnalyzeType<Length<LEN>> + MakeBlob + MaybeHas<DecodeLimits>,
+    T: BlobElement<M::Endian>,
+    LimitError: Into<M::Error>,
 {
     fn make_with(maker: &mut M) -> Result<BigArr<T, LEN>, M::Error> {
         let len: LEN = maker.make_type()?;
         let len = maker.type_dynamic_size(&Length(len));
+        limits::allocate::<M, T>(maker, len)?;
         let vec: Vec<T> = maker.make_blob(len)?;
         Ok(BigArr(vec, Default::default()))
     }
 }
 
-impl<V, T: bytemuck::Pod, LEN> VisitWith<V> for BigArr<T, LEN>
+impl<V, T, LEN> VisitWith<V> for BigArr<T, LEN>
 where
     V: VisitType<LEN> + VisitBlob,
+    T: BlobElement<V::Endian>,
     usize: TryInto<LEN, Error: Into<V::Error>>,
 {
     fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
@@ -109,11 +126,197 @@ where
     }
 }
 
+// endregion
+// region: Arr
+
+/// Same as [`BigArr`], but makes and visits elements one by one, so `T` doesn't have to be Pod.
+pub struct Arr<T, LEN>(pub Vec<T>, pub(crate) PhantomData<LEN>);
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, LEN> serde::Deserialize<'de> for Arr<T, LEN> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self(Vec::<T>::deserialize(deserializer)?, PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, LEN> serde::Serialize for Arr<T, LEN> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T: Clone, LEN> Clone for Arr<T, LEN> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T, LEN> Default for Arr<T, LEN> {
+    fn default() -> Self {
+        Self(Default::default(), Default::default())
+    }
+}
+
+impl<T, LEN> Arr<T, LEN> {
+    pub fn new_unchecked(vec: Vec<T>) -> Self {
+        Self(vec, PhantomData)
+    }
+}
+
+impl<T, LEN> From<BigArr<T, LEN>> for Arr<T, LEN> {
+    fn from(arr: BigArr<T, LEN>) -> Self {
+        Self(arr.0, PhantomData)
+    }
+}
+
+impl<T, LEN> From<Arr<T, LEN>> for BigArr<T, LEN> {
+    fn from(arr: Arr<T, LEN>) -> Self {
+        Self(arr.0, PhantomData)
+    }
+}
+
+impl<T: fmt::Debug, LEN> fmt::Debug for Arr<T, LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_arr("Vec", &self.0, f)
+    }
+}
+
+impl<D, T, LEN> AnalyzeWith<D> for Arr<T, LEN>
+where
+    D: TypeAnalyzer + AnalyzeType<T> + AnalyzeType<LEN> + AnalyzeType<Length<LEN>>,
+{
+    const CONST_SIZE: TypeSize = repeat_size_and_len(
+        <D as AnalyzeType<T>>::TYPE_CONST_SIZE,
+        <D as AnalyzeType<Length<LEN>>>::TYPE_CONST_SIZE,
+        <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE,
+    );
+
+    represent::expect_sizes!(D);
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        let reps = <D as AnalyzeType<Length<LEN>>>::type_try_fixed_size(analyzer)?;
+        let bytes = <D as AnalyzeType<T>>::type_try_fixed_size(analyzer)?;
+        let header = <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE.expect_const();
+        checked_repeat_size(header, reps, bytes)
+    }
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        let header = <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE.expect_const();
+        let elements = self
+            .0
+            .iter()
+            .map(|element| analyzer.type_try_dynamic_size(element));
+        checked_sum(std::iter::once(Ok(header)).chain(elements))
+    }
+}
+
+impl<M, T, LEN> MakeWith<M> for Arr<T, LEN>
+where
+    M: MakeType<LEN> + MakeType<T> + AnalyzeType<Length<LEN>> + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Arr<T, LEN>, M::Error> {
+        let len: LEN = maker.make_type()?;
+        let len = maker.type_dynamic_size(&Length(len));
+        limits::allocate::<M, T>(maker, len)?;
+        let vec = limits::nested(maker, |maker| {
+            let mut vec = Vec::<T>::with_capacity(len);
+            for index in 0..len {
+                vec.push(maker.make_keyed(index)?);
+            }
+            Ok(vec)
+        })?;
+        Ok(Arr(vec, Default::default()))
+    }
+}
+
+impl<V, T, LEN> VisitWith<V> for Arr<T, LEN>
+where
+    V: VisitType<LEN> + VisitType<T>,
+    usize: TryInto<LEN, Error: Into<V::Error>>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        let len: LEN = self.0.len().try_into().map_err(Into::into)?;
+        visitor.visit(&len)?;
+        for (index, element) in self.0.iter().enumerate() {
+            visitor.visit_keyed(index, element)?;
+        }
+        Ok(())
+    }
+}
+
+impl<M, T, LEN> MakeRecursive<M, T> for Arr<T, LEN>
+where
+    M: MakeType<LEN> + AnalyzeType<Length<LEN>> + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    fn make_recursive(
+        maker: &mut M,
+        mut element: impl FnMut(&mut M) -> Result<T, M::Error>,
+    ) -> Result<Self, M::Error> {
+        let len: LEN = maker.make_type()?;
+        let len = maker.type_dynamic_size(&Length(len));
+        limits::allocate::<M, T>(maker, len)?;
+        let mut vec = Vec::<T>::with_capacity(len);
+        for index in 0..len {
+            vec.push(maker.with_key(index.into(), &mut element)?);
+        }
+        Ok(Arr(vec, Default::default()))
+    }
+}
+
+impl<V, T, LEN> VisitRecursive<V, T> for Arr<T, LEN>
+where
+    V: VisitType<LEN> + Visitor,
+    usize: TryInto<LEN, Error: Into<V::Error>>,
+{
+    fn visit_recursive(
+        &self,
+        visitor: &mut V,
+        mut element: impl FnMut(&T, &mut V) -> Result<(), V::Error>,
+    ) -> Result<(), V::Error> {
+        let len: LEN = self.0.len().try_into().map_err(Into::into)?;
+        visitor.visit(&len)?;
+        for (index, rep) in self.0.iter().enumerate() {
+            visitor.with_key(index.into(), |visitor| element(rep, visitor))?;
+        }
+        Ok(())
+    }
+}
+
+impl<D, T, LEN> AnalyzeRecursive<D, T> for Arr<T, LEN>
+where
+    D: TypeAnalyzer + AnalyzeType<LEN>,
+{
+    fn try_recursive_size(
+        &self,
+        analyzer: &D,
+        mut element: impl FnMut(&T, &D) -> Result<usize, SizeOverflow>,
+    ) -> Result<usize, SizeOverflow> {
+        let header = <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE.expect_const();
+        let body = self.0.iter().map(|rep| element(rep, analyzer));
+        checked_sum(std::iter::once(Ok(header)).chain(body))
+    }
+}
+
 // endregion
 // region: BigStr
 
-#[derive(Clone)]
-pub struct BigStr<LEN>(pub BigArr<u8, LEN>);
+/// Bytes of the string are stored as is, `ENC` is only applied by conversions and `Debug`.
+pub struct BigStr<LEN, ENC = Utf8>(pub BigArr<u8, LEN>, pub(crate) PhantomData<ENC>);
+
+impl<LEN, ENC> Clone for BigStr<LEN, ENC> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
 
 #[cfg_attr(
     feature = "serde",
@@ -122,7 +325,7 @@ pub struct BigStr<LEN>(pub BigArr<u8, LEN>);
 )]
 enum MaybeStr<'a> {
     Utf8 {
-        string: &'a str,
+        string: Cow<'a, str>,
         #[cfg_attr(
             feature = "serde",
             serde(default, skip_serializing_if = "impl_serde::is_zero")
@@ -164,26 +367,30 @@ mod impl_serde {
         *val == 0
     }
 
-    impl<'de, LEN> serde::Deserialize<'de> for BigStr<LEN> {
+    impl<'de, LEN, ENC: Encoding> serde::Deserialize<'de> for BigStr<LEN, ENC> {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: serde::Deserializer<'de>,
         {
             let maybe_str = MaybeStr::deserialize(deserializer)?;
-            let (bytes, tail_zeros) = match maybe_str {
-                MaybeStr::Utf8 { string, tail_zeros } => (string.as_bytes(), tail_zeros),
-                MaybeStr::Bytes { bytes, tail_zeros } => (bytes, tail_zeros),
+            let (bytes, tail_zeros) = match &maybe_str {
+                MaybeStr::Utf8 { string, tail_zeros } => (
+                    ENC::encode(string)
+                        .map_err(|err| serde::de::Error::custom(format_args!("{:?}", err)))?,
+                    *tail_zeros,
+                ),
+                MaybeStr::Bytes { bytes, tail_zeros } => (Cow::Borrowed(*bytes), *tail_zeros),
             };
             let mut vec = Vec::with_capacity(bytes.len() + tail_zeros);
-            vec.extend_from_slice(bytes);
+            vec.extend_from_slice(&bytes);
             if tail_zeros > 0 {
                 vec.resize(vec.len() + tail_zeros, 0);
             }
-            Ok(Self(BigArr::new_unchecked(vec)))
+            Ok(Self::new_unchecked(vec))
         }
     }
 
-    impl<LEN> serde::Serialize for BigStr<LEN> {
+    impl<LEN, ENC: Encoding> serde::Serialize for BigStr<LEN, ENC> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: serde::Serializer,
@@ -193,26 +400,50 @@ mod impl_serde {
     }
 }
 
-impl<LEN> BigStr<LEN> {
+impl<LEN, ENC> BigStr<LEN, ENC> {
     pub fn new_unchecked(vec: Vec<u8>) -> Self {
-        Self(BigArr::new_unchecked(vec))
+        Self(BigArr::new_unchecked(vec), PhantomData)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0.0
+    }
+}
+
+impl<LEN, ENC: Encoding> BigStr<LEN, ENC> {
+    /// Decodes the whole string, including any padding.
+    pub fn as_str(&self) -> Result<Cow<'_, str>, EncodingError> {
+        ENC::decode(&self.0.0)
     }
 
-    fn as_maybe_str(&self) -> MaybeStr {
+    fn as_maybe_str(&self) -> MaybeStr<'_> {
         let slice = &self.0.0[..];
-        let last_non_zero = slice.iter().rposition(|ch| *ch != 0).unwrap_or(0);
-        let tail_zeros = slice.len() - last_non_zero;
-        match std::str::from_utf8(&slice[..last_non_zero]) {
+        let unit = ENC::UNIT_SIZE;
+        let mut end = slice.len();
+        while end >= unit && slice[end - unit..end].iter().all(|ch| *ch == 0) {
+            end -= unit;
+        }
+        let tail_zeros = slice.len() - end;
+        match ENC::decode(&slice[..end]) {
             Ok(string) => MaybeStr::Utf8 { string, tail_zeros },
             _ => MaybeStr::Bytes {
-                bytes: slice,
+                bytes: &slice[..end],
                 tail_zeros,
             },
         }
     }
 }
 
-impl<LEN> BigStr<LEN> {
+/// Length is not checked, same as with [`BigStr::new_unchecked`].
+impl<LEN, ENC: Encoding> FromStr for BigStr<LEN, ENC> {
+    type Err = EncodingError;
+
+    fn from_str(string: &str) -> Result<Self, EncodingError> {
+        Ok(Self::new_unchecked(ENC::encode(string)?.into_owned()))
+    }
+}
+
+impl<LEN, ENC> BigStr<LEN, ENC> {
     pub fn new_fixed<A: TypeAnalyzer>(vec: Vec<u8>, analyzer: &A) -> Result<Self, LengthError>
     where
         Length<LEN>: AnalyzeWith<A>,
@@ -223,7 +454,7 @@ impl<LEN> BigStr<LEN> {
         if verified != from {
             Err(LengthError::Verify { from, verified })
         } else {
-            Ok(Self(BigArr::new_unchecked(vec)))
+            Ok(Self::new_unchecked(vec))
         }
     }
 
@@ -242,11 +473,11 @@ impl<LEN> BigStr<LEN> {
             return Err(LengthError::Verify { from, verified });
         }
         vec.resize(verified, fill);
-        Ok(Self(BigArr::new_unchecked(vec)))
+        Ok(Self::new_unchecked(vec))
     }
 }
 
-impl<LEN> fmt::Debug for BigStr<LEN> {
+impl<LEN, ENC: Encoding> fmt::Debug for BigStr<LEN, ENC> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let str = self.as_maybe_str();
 
@@ -254,7 +485,10 @@ impl<LEN> fmt::Debug for BigStr<LEN> {
     }
 }
 
-impl<LEN, D: TypeAnalyzer + AnalyzeType<BigArr<u8, LEN>>> AnalyzeWith<D> for BigStr<LEN> {
+impl<LEN, ENC, D> AnalyzeWith<D> for BigStr<LEN, ENC>
+where
+    D: TypeAnalyzer + AnalyzeType<BigArr<u8, LEN>>,
+{
     const CONST_SIZE: TypeSize = D::TYPE_CONST_SIZE;
 
     fn fixed_size(analyzer: &D) -> usize {
@@ -264,18 +498,26 @@ impl<LEN, D: TypeAnalyzer + AnalyzeType<BigArr<u8, LEN>>> AnalyzeWith<D> for Big
     fn dynamic_size(&self, analyzer: &D) -> usize {
         analyzer.type_dynamic_size(&self.0)
     }
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        D::type_try_fixed_size(analyzer)
+    }
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        analyzer.type_try_dynamic_size(&self.0)
+    }
 }
 
-impl<M, LEN> MakeWith<M> for BigStr<LEN>
+impl<M, LEN, ENC> MakeWith<M> for BigStr<LEN, ENC>
 where
     M: MakeType<BigArr<u8, LEN>>,
 {
-    fn make_with(maker: &mut M) -> Result<BigStr<LEN>, M::Error> {
-        Ok(BigStr(maker.make_type()?))
+    fn make_with(maker: &mut M) -> Result<BigStr<LEN, ENC>, M::Error> {
+        Ok(BigStr(maker.make_type()?, PhantomData))
     }
 }
 
-impl<V, LEN> VisitWith<V> for BigStr<LEN>
+impl<V, LEN, ENC> VisitWith<V> for BigStr<LEN, ENC>
 where
     V: VisitType<BigArr<u8, LEN>>,
 {
@@ -285,6 +527,135 @@ where
 }
 
 // endregiond
+// region: CStr
+
+/// Zero-terminated string, `MAX` limits its length without the terminator.
+/// Made through [`MakeBlob::make_raw_until`] and visited through [`VisitBlob`].
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct CStr<const MAX: usize = { usize::MAX }>(pub Vec<u8>);
+
+#[derive(Debug)]
+pub enum CStrError {
+    InteriorNul { position: usize },
+    TooLong { len: usize, max: usize },
+}
+
+impl<const MAX: usize> CStr<MAX> {
+    pub fn new(vec: Vec<u8>) -> Result<Self, CStrError> {
+        if let Some(position) = vec.iter().position(|ch| *ch == 0) {
+            return Err(CStrError::InteriorNul { position });
+        }
+        if vec.len() > MAX {
+            return Err(CStrError::TooLong {
+                len: vec.len(),
+                max: MAX,
+            });
+        }
+        Ok(Self(vec))
+    }
+
+    /// bytes without the terminator
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn as_maybe_str(&self) -> MaybeStr<'_> {
+        match std::str::from_utf8(&self.0) {
+            Ok(string) => MaybeStr::Utf8 {
+                string: Cow::Borrowed(string),
+                tail_zeros: 0,
+            },
+            _ => MaybeStr::Bytes {
+                bytes: &self.0,
+                tail_zeros: 0,
+            },
+        }
+    }
+}
+
+impl<const MAX: usize> fmt::Debug for CStr<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <MaybeStr as std::fmt::Debug>::fmt(&self.as_maybe_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const MAX: usize> serde::Deserialize<'de> for CStr<MAX> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = match MaybeStr::deserialize(deserializer)? {
+            MaybeStr::Utf8 { string, .. } => string.into_owned().into_bytes(),
+            MaybeStr::Bytes { bytes, .. } => bytes.to_vec(),
+        };
+        Self::new(bytes).map_err(|err| serde::de::Error::custom(format_args!("{:?}", err)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MAX: usize> serde::Serialize for CStr<MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_maybe_str().serialize(serializer)
+    }
+}
+
+impl<D: TypeAnalyzer, const MAX: usize> AnalyzeWith<D> for CStr<MAX> {
+    const CONST_SIZE: TypeSize = TypeSize::Dynamic;
+
+    represent::expect_sizes!(dynamic D);
+
+    fn try_dynamic_size(&self, _analyzer: &D) -> Result<usize, SizeOverflow> {
+        self.0.len().checked_add(1).ok_or(SizeOverflow)
+    }
+}
+
+impl<M, const MAX: usize> MakeWith<M> for CStr<MAX>
+where
+    M: MakeBlob,
+    CStrError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<CStr<MAX>, M::Error> {
+        match maker.make_raw_until(0, MAX)? {
+            Some(vec) => Ok(CStr(vec)),
+            None => Err(CStrError::TooLong {
+                len: MAX + 1,
+                max: MAX,
+            }
+            .into()),
+        }
+    }
+}
+
+impl<V, const MAX: usize> VisitWith<V> for CStr<MAX>
+where
+    V: VisitBlob,
+    CStrError: Into<V::Error>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Some(position) = self.0.iter().position(|ch| *ch == 0) {
+            return Err(CStrError::InteriorNul { position }.into());
+        }
+        if self.0.len() > MAX {
+            return Err(CStrError::TooLong {
+                len: self.0.len(),
+                max: MAX,
+            }
+            .into());
+        }
+        visitor.visit_raw_blob(&self.0)?;
+        visitor.visit_raw_blob(&[0u8])
+    }
+}
+
+// endregion
 
 fn debug_arr<T: fmt::Debug>(name: &str, arr: &[T], f: &mut fmt::Formatter<'_>) -> fmt::Result {
     const HALF_MAX: usize = 16;
@@ -1,19 +1,35 @@
-use std::{convert::TryFrom, marker::PhantomData};
+use std::{
+    collections::{BTreeMap, HashMap, btree_map, hash_map},
+    convert::TryFrom,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
 
 use represent::{
-    AnalyzeWith, MakeType, MakeWith, Maker, TypeAnalyzer, TypeSize, VisitType, VisitWith, Visitor,
+    AnalyzeRecursive, AnalyzeWith, MakeRecursive, MakeType, MakeWith, Maker, SizeOverflow,
+    TypeAnalyzer, TypeSize, VisitRecursive, VisitType, VisitWith, Visitor, checked_repeat_size,
+    checked_sum, const_repeat_size, sum_sizes,
 };
 
-use super::length::{Length, LengthError};
+use super::{
+    MaybeHas,
+    length::{Length, LengthError},
+    limits::{self, DecodeLimits, LimitError},
+};
+use crate::{
+    endian::BlobElement,
+    traits::{MakeBlob, VisitBlob},
+};
 //use crate::{Encrypt, Farter, MakeError, Sniffer, VisitError};
 
 // region: RepeatExt
 
 #[derive(derivative::Derivative, Clone)]
 #[derivative(Debug)]
-pub struct RepeatExt<T, LEN>(
+pub struct RepeatExt<T, LEN, MODE = PerElement>(
     pub Vec<T>,
-    #[derivative(Debug = "ignore")] pub(crate) PhantomData<LEN>,
+    #[derivative(Debug = "ignore")] pub(crate) PhantomData<(LEN, MODE)>,
 );
 
 #[cfg(feature = "serde")]
@@ -22,7 +38,7 @@ mod impl_serde {
 
     use super::*;
 
-    impl<'de, T: Deserialize<'de>, LEN> Deserialize<'de> for RepeatExt<T, LEN> {
+    impl<'de, T: Deserialize<'de>, LEN, MODE> Deserialize<'de> for RepeatExt<T, LEN, MODE> {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
@@ -31,7 +47,25 @@ mod impl_serde {
         }
     }
 
-    impl<T: Serialize, LEN> serde::Serialize for RepeatExt<T, LEN> {
+    impl<T: Serialize, LEN, MODE> serde::Serialize for RepeatExt<T, LEN, MODE> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, K, V, LEN, MAP: Deserialize<'de>> Deserialize<'de> for MapExt<K, V, LEN, MAP> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Self(MAP::deserialize(deserializer)?, PhantomData))
+        }
+    }
+
+    impl<K, V, LEN, MAP: Serialize> serde::Serialize for MapExt<K, V, LEN, MAP> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: Serializer,
@@ -41,73 +75,369 @@ mod impl_serde {
     }
 }
 
-impl<T, LEN> RepeatExt<T, LEN> {
+impl<T, LEN, MODE> RepeatExt<T, LEN, MODE> {
     pub fn new_unchecked(values: Vec<T>) -> Self {
         Self(values, PhantomData)
     }
 }
 
-impl<D: TypeAnalyzer, T: AnalyzeWith<D>, LEN: AnalyzeWith<D>> AnalyzeWith<D> for RepeatExt<T, LEN>
+impl<D, T, LEN, MODE> AnalyzeWith<D> for RepeatExt<T, LEN, MODE>
 where
+    D: TypeAnalyzer,
+    T: AnalyzeWith<D>,
+    LEN: AnalyzeWith<D>,
     Length<LEN>: AnalyzeWith<D>,
 {
     const CONST_SIZE: TypeSize =
         repeat_size_and_len(T::CONST_SIZE, Length::<LEN>::CONST_SIZE, LEN::CONST_SIZE);
 
-    fn fixed_size(analyzer: &D) -> usize {
-        let reps = LEN::fixed_size(analyzer);
-        let bytes = T::fixed_size(analyzer);
+    represent::expect_sizes!(D);
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        let reps = Length::<LEN>::try_fixed_size(analyzer)?;
+        let bytes = T::try_fixed_size(analyzer)?;
         let header = LEN::CONST_SIZE.expect_const();
-        header + reps * bytes
+        checked_repeat_size(header, reps, bytes)
     }
 
-    fn dynamic_size(&self, analyzer: &D) -> usize {
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
         let header = LEN::CONST_SIZE.expect_const();
-        let body: usize = self.0.iter().map(|rep| rep.dynamic_size(analyzer)).sum();
-        header + body
+        let body = self.0.iter().map(|rep| rep.try_dynamic_size(analyzer));
+        checked_sum(std::iter::once(Ok(header)).chain(body))
     }
 }
 
-const fn repeat_size_and_len(single: TypeSize, reps: TypeSize, header: TypeSize) -> TypeSize {
+pub(crate) const fn repeat_size_and_len(
+    single: TypeSize,
+    reps: TypeSize,
+    header: TypeSize,
+) -> TypeSize {
     let header = header.expect_const();
     match (single, reps) {
-        (TypeSize::Const(bytes), TypeSize::Const(size)) => TypeSize::Const(header + bytes * size),
+        (TypeSize::Const(bytes), TypeSize::Const(size)) => {
+            TypeSize::Const(const_repeat_size(header, size, bytes))
+        }
         (TypeSize::Dynamic, _) => TypeSize::Dynamic,
         (_, TypeSize::Dynamic) => TypeSize::Dynamic,
         _ => TypeSize::Fixed,
     }
 }
 
-impl<M, T, LEN> MakeWith<M> for RepeatExt<T, LEN>
+impl<M, T, LEN, MODE: MakeRepeat<M, T>> MakeWith<M> for RepeatExt<T, LEN, MODE>
 where
     Length<LEN>: AnalyzeWith<M>,
-    M: MakeType<LEN> + MakeType<T> + Maker + TypeAnalyzer,
+    M: MakeType<LEN> + Maker + TypeAnalyzer + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
 {
-    fn make_with(maker: &mut M) -> Result<RepeatExt<T, LEN>, M::Error> {
+    fn make_with(maker: &mut M) -> Result<RepeatExt<T, LEN, MODE>, M::Error> {
         let len: LEN = maker.make_type()?;
         let len = Length(len).dynamic_size(maker);
+        limits::allocate::<M, T>(maker, len)?;
+        let vec = MODE::make_repeat(maker, len)?;
+        Ok(RepeatExt(vec, Default::default()))
+    }
+}
+
+impl<V: Visitor, T, LEN, MODE: VisitRepeat<V, T>> VisitWith<V> for RepeatExt<T, LEN, MODE>
+where
+    LEN: TryFrom<usize, Error = LengthError>,
+    V: VisitType<LEN> + Visitor,
+    <LEN as TryFrom<usize>>::Error: Into<V::Error>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        let len = LEN::try_from(self.0.len()).map_err(Into::into)?;
+        visitor.visit(&len)?;
+        MODE::visit_repeat(visitor, &self.0)
+    }
+}
+
+impl<M, T, LEN> MakeRecursive<M, T> for RepeatExt<T, LEN, PerElement>
+where
+    Length<LEN>: AnalyzeWith<M>,
+    M: MakeType<LEN> + Maker + TypeAnalyzer + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    fn make_recursive(
+        maker: &mut M,
+        mut element: impl FnMut(&mut M) -> Result<T, M::Error>,
+    ) -> Result<Self, M::Error> {
+        let len: LEN = maker.make_type()?;
+        let len = Length(len).dynamic_size(maker);
+        limits::allocate::<M, T>(maker, len)?;
         let mut vec = Vec::<T>::with_capacity(len);
         for index in 0..len {
-            vec.push(maker.make_keyed(index)?);
+            vec.push(maker.with_key(index.into(), &mut element)?);
         }
         Ok(RepeatExt(vec, Default::default()))
     }
 }
 
-impl<V: Visitor, T, LEN> VisitWith<V> for RepeatExt<T, LEN>
+impl<V: Visitor, T, LEN> VisitRecursive<V, T> for RepeatExt<T, LEN, PerElement>
 where
     LEN: TryFrom<usize, Error = LengthError>,
-    V: VisitType<LEN> + VisitType<T> + Visitor,
+    V: VisitType<LEN> + Visitor,
     <LEN as TryFrom<usize>>::Error: Into<V::Error>,
 {
-    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+    fn visit_recursive(
+        &self,
+        visitor: &mut V,
+        mut element: impl FnMut(&T, &mut V) -> Result<(), V::Error>,
+    ) -> Result<(), V::Error> {
         let len = LEN::try_from(self.0.len()).map_err(Into::into)?;
         visitor.visit(&len)?;
-        for (index, element) in self.0.iter().enumerate() {
+        for (index, rep) in self.0.iter().enumerate() {
+            visitor.with_key(index.into(), |visitor| element(rep, visitor))?;
+        }
+        Ok(())
+    }
+}
+
+impl<D, T, LEN> AnalyzeRecursive<D, T> for RepeatExt<T, LEN, PerElement>
+where
+    D: TypeAnalyzer,
+    LEN: AnalyzeWith<D>,
+{
+    fn try_recursive_size(
+        &self,
+        analyzer: &D,
+        mut element: impl FnMut(&T, &D) -> Result<usize, SizeOverflow>,
+    ) -> Result<usize, SizeOverflow> {
+        let header = LEN::CONST_SIZE.expect_const();
+        let body = self.0.iter().map(|rep| element(rep, analyzer));
+        checked_sum(std::iter::once(Ok(header)).chain(body))
+    }
+}
+
+pub trait MakeRepeat<M: Maker, T> {
+    fn make_repeat(maker: &mut M, len: usize) -> Result<Vec<T>, M::Error>;
+}
+
+pub trait VisitRepeat<V: Visitor, T> {
+    fn visit_repeat(visitor: &mut V, elements: &[T]) -> Result<(), V::Error>;
+}
+
+/// Elements are made and visited one by one, keyed by index.
+#[derive(Debug, Default, Clone)]
+pub struct PerElement;
+
+impl<M, T> MakeRepeat<M, T> for PerElement
+where
+    M: Maker + MakeType<T> + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    fn make_repeat(maker: &mut M, len: usize) -> Result<Vec<T>, M::Error> {
+        limits::nested(maker, |maker| {
+            let mut vec = Vec::<T>::with_capacity(len);
+            for index in 0..len {
+                vec.push(maker.make_keyed(index)?);
+            }
+            Ok(vec)
+        })
+    }
+}
+
+impl<V: Visitor + VisitType<T>, T> VisitRepeat<V, T> for PerElement {
+    fn visit_repeat(visitor: &mut V, elements: &[T]) -> Result<(), V::Error> {
+        for (index, element) in elements.iter().enumerate() {
             visitor.visit_keyed(index, element)?;
         }
         Ok(())
     }
 }
 
+/// Elements are made and visited as a single blob, without per-element keys.
+///
+/// Element's analyzed size must be `Const` and equal to its in-memory size.
+#[derive(Debug, Default, Clone)]
+pub struct Bulk;
+
+struct AssertBulk<A, T>(PhantomData<A>, PhantomData<T>);
+impl<A: TypeAnalyzer, T: AnalyzeWith<A>> AssertBulk<A, T> {
+    const ASSERT_BULK: () = assert!(
+        matches!(T::CONST_SIZE, TypeSize::Const(size) if size == std::mem::size_of::<T>()),
+        "Bulk elements should have const size equal to their in-memory size"
+    );
+
+    #[expect(path_statements)]
+    fn assert() {
+        Self::ASSERT_BULK;
+    }
+}
+
+impl<M, T> MakeRepeat<M, T> for Bulk
+where
+    M: MakeBlob + TypeAnalyzer,
+    T: BlobElement<M::Endian> + AnalyzeWith<M>,
+{
+    fn make_repeat(maker: &mut M, len: usize) -> Result<Vec<T>, M::Error> {
+        AssertBulk::<M, T>::assert();
+        maker.make_blob(len)
+    }
+}
+
+impl<V: VisitBlob, T: BlobElement<V::Endian>> VisitRepeat<V, T> for Bulk {
+    fn visit_repeat(visitor: &mut V, elements: &[T]) -> Result<(), V::Error> {
+        visitor.visit_blob(elements)
+    }
+}
+
+// endregion
+// region: MapExt
+
+pub trait MapLike<K, V>: Default {
+    /// returns the entry back if the key is already present
+    fn insert_new(&mut self, key: K, value: V) -> Result<(), (K, V)>;
+    /// entries in deterministic (sorted by key) order
+    fn sorted_entries(&self) -> Vec<(&K, &V)>;
+}
+
+impl<K: Ord, V> MapLike<K, V> for BTreeMap<K, V> {
+    fn insert_new(&mut self, key: K, value: V) -> Result<(), (K, V)> {
+        match self.entry(key) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+            btree_map::Entry::Occupied(entry) => Err((entry.remove_entry().0, value)),
+        }
+    }
+
+    fn sorted_entries(&self) -> Vec<(&K, &V)> {
+        self.iter().collect()
+    }
+}
+
+impl<K: Ord + Hash, V, S: BuildHasher + Default> MapLike<K, V> for HashMap<K, V, S> {
+    fn insert_new(&mut self, key: K, value: V) -> Result<(), (K, V)> {
+        match self.entry(key) {
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+            hash_map::Entry::Occupied(entry) => Err((entry.remove_entry().0, value)),
+        }
+    }
+
+    fn sorted_entries(&self) -> Vec<(&K, &V)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        entries
+    }
+}
+
+/// Map visited in key order, keys are keyed by their index and values by the key's `Display`.
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "MAP: fmt::Debug"), Clone(bound = "MAP: Clone"))]
+pub struct MapExt<K, V, LEN, MAP = BTreeMap<K, V>>(
+    pub MAP,
+    #[derivative(Debug = "ignore")] pub(crate) PhantomData<(K, V, LEN)>,
+);
+
+impl<K, V, LEN, MAP> MapExt<K, V, LEN, MAP> {
+    pub fn new_unchecked(map: MAP) -> Self {
+        Self(map, PhantomData)
+    }
+}
+
+impl<D, K, V, LEN, MAP> AnalyzeWith<D> for MapExt<K, V, LEN, MAP>
+where
+    D: TypeAnalyzer,
+    K: AnalyzeWith<D>,
+    V: AnalyzeWith<D>,
+    LEN: AnalyzeWith<D>,
+    Length<LEN>: AnalyzeWith<D>,
+    MAP: MapLike<K, V>,
+    for<'a> &'a MAP: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    const CONST_SIZE: TypeSize = repeat_size_and_len(
+        sum_sizes([K::CONST_SIZE, V::CONST_SIZE]),
+        Length::<LEN>::CONST_SIZE,
+        LEN::CONST_SIZE,
+    );
+
+    represent::expect_sizes!(D);
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        let reps = Length::<LEN>::try_fixed_size(analyzer)?;
+        let bytes = checked_sum([K::try_fixed_size(analyzer), V::try_fixed_size(analyzer)])?;
+        let header = LEN::CONST_SIZE.expect_const();
+        checked_repeat_size(header, reps, bytes)
+    }
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        let header = LEN::CONST_SIZE.expect_const();
+        let body = self.0.into_iter().flat_map(|(key, value)| {
+            [
+                key.try_dynamic_size(analyzer),
+                value.try_dynamic_size(analyzer),
+            ]
+        });
+        checked_sum(std::iter::once(Ok(header)).chain(body))
+    }
+}
+
+#[derive(Debug)]
+pub enum MapError {
+    DuplicateKey {
+        index: usize,
+        key: String,
+        type_name: &'static str,
+    },
+}
+
+impl<M, K, V, LEN, MAP> MakeWith<M> for MapExt<K, V, LEN, MAP>
+where
+    Length<LEN>: AnalyzeWith<M>,
+    M: MakeType<LEN> + MakeType<K> + MakeType<V> + Maker + TypeAnalyzer,
+    M: MaybeHas<DecodeLimits>,
+    K: fmt::Display,
+    MAP: MapLike<K, V>,
+    MapError: Into<M::Error>,
+    LimitError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<MapExt<K, V, LEN, MAP>, M::Error> {
+        let len: LEN = maker.make_type()?;
+        let len = Length(len).dynamic_size(maker);
+        limits::allocate::<M, (K, V)>(maker, len)?;
+        let map = limits::nested(maker, |maker| {
+            let mut map = MAP::default();
+            for index in 0..len {
+                // key is unknown until it is made, so it's keyed by index and its value by the key
+                let key: K = maker.make_keyed(index)?;
+                let value: V = maker.make_keyed(key.to_string())?;
+                if let Err((key, _)) = map.insert_new(key, value) {
+                    return Err(MapError::DuplicateKey {
+                        index,
+                        key: key.to_string(),
+                        type_name: std::any::type_name::<MAP>(),
+                    }
+                    .into());
+                }
+            }
+            Ok(map)
+        })?;
+        Ok(MapExt(map, Default::default()))
+    }
+}
+
+impl<VI: Visitor, K, V, LEN, MAP> VisitWith<VI> for MapExt<K, V, LEN, MAP>
+where
+    LEN: TryFrom<usize, Error = LengthError>,
+    VI: VisitType<LEN> + VisitType<K> + VisitType<V> + Visitor,
+    <LEN as TryFrom<usize>>::Error: Into<VI::Error>,
+    K: fmt::Display,
+    MAP: MapLike<K, V>,
+{
+    fn visit_with(&self, visitor: &mut VI) -> Result<(), VI::Error> {
+        let entries = self.0.sorted_entries();
+        let len = LEN::try_from(entries.len()).map_err(Into::into)?;
+        visitor.visit(&len)?;
+        for (index, (key, value)) in entries.into_iter().enumerate() {
+            visitor.visit_keyed(index, key)?;
+            visitor.visit_keyed(key.to_string(), value)?;
+        }
+        Ok(())
+    }
+}
+
 // endregion
@@ -1,7 +1,8 @@
 use std::marker::PhantomData;
 
 use represent::{
-    AnalyzeWith, MakeType, MakeWith, Maker, TypeAnalyzer, TypeSize, VisitType, VisitWith, Visitor,
+    AnalyzeWith, MakeType, MakeWith, Maker, SizeOverflow, TypeAnalyzer, TypeSize, VisitType,
+    VisitWith, Visitor,
 };
 
 use super::{slots::Slots, Has};
@@ -51,6 +52,21 @@ impl<D: TypeAnalyzer, C: Condition<D>, T: AnalyzeWith<D>> AnalyzeWith<D> for Con
             None => 0,
         }
     }
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        if C::check(analyzer) {
+            T::try_fixed_size(analyzer)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        match &self.0 {
+            Some(inner) => inner.try_dynamic_size(analyzer),
+            None => Ok(0),
+        }
+    }
 }
 
 const fn fixed(is: bool) -> TypeSize {
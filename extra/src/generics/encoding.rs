@@ -0,0 +1,183 @@
+use std::{borrow::Cow, convert::TryFrom, marker::PhantomData};
+
+use crate::endian::{BigEndian, Endian, LittleEndian};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// bytes at `position` can't be decoded
+    Invalid {
+        position: usize,
+        encoding: &'static str,
+    },
+    /// char at byte `position` of the string can't be encoded
+    Unrepresentable {
+        ch: char,
+        position: usize,
+        encoding: &'static str,
+    },
+}
+
+pub trait Encoding {
+    const NAME: &'static str;
+    /// size of a single code unit in bytes, padding is trimmed by whole units
+    const UNIT_SIZE: usize = 1;
+
+    fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, EncodingError>;
+    fn encode(string: &str) -> Result<Cow<'_, [u8]>, EncodingError>;
+}
+
+// region: Utf8
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Utf8;
+
+impl Encoding for Utf8 {
+    const NAME: &'static str = "UTF-8";
+
+    fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, EncodingError> {
+        std::str::from_utf8(bytes)
+            .map(Cow::Borrowed)
+            .map_err(|err| EncodingError::Invalid {
+                position: err.valid_up_to(),
+                encoding: Self::NAME,
+            })
+    }
+
+    fn encode(string: &str) -> Result<Cow<'_, [u8]>, EncodingError> {
+        Ok(Cow::Borrowed(string.as_bytes()))
+    }
+}
+
+// endregion
+// region: Ascii
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ascii;
+
+impl Encoding for Ascii {
+    const NAME: &'static str = "ASCII";
+
+    fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, EncodingError> {
+        match bytes.iter().position(|byte| !byte.is_ascii()) {
+            Some(position) => Err(EncodingError::Invalid {
+                position,
+                encoding: Self::NAME,
+            }),
+            None => Utf8::decode(bytes),
+        }
+    }
+
+    fn encode(string: &str) -> Result<Cow<'_, [u8]>, EncodingError> {
+        match string.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+            Some((position, ch)) => Err(EncodingError::Unrepresentable {
+                ch,
+                position,
+                encoding: Self::NAME,
+            }),
+            None => Ok(Cow::Borrowed(string.as_bytes())),
+        }
+    }
+}
+
+// endregion
+// region: Latin1
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Latin1;
+
+impl Encoding for Latin1 {
+    const NAME: &'static str = "Latin-1";
+
+    fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, EncodingError> {
+        if bytes.is_ascii() {
+            Utf8::decode(bytes)
+        } else {
+            Ok(Cow::Owned(
+                bytes.iter().map(|byte| char::from(*byte)).collect(),
+            ))
+        }
+    }
+
+    fn encode(string: &str) -> Result<Cow<'_, [u8]>, EncodingError> {
+        if string.is_ascii() {
+            return Ok(Cow::Borrowed(string.as_bytes()));
+        }
+        string
+            .char_indices()
+            .map(|(position, ch)| {
+                u8::try_from(u32::from(ch)).map_err(|_| EncodingError::Unrepresentable {
+                    ch,
+                    position,
+                    encoding: Self::NAME,
+                })
+            })
+            .collect::<Result<Vec<u8>, _>>()
+            .map(Cow::Owned)
+    }
+}
+
+// endregion
+// region: Utf16
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Utf16<E>(PhantomData<E>);
+
+pub type Utf16Le = Utf16<LittleEndian>;
+pub type Utf16Be = Utf16<BigEndian>;
+
+impl<E: Endian> Utf16<E> {
+    fn unit(bytes: &[u8]) -> u16 {
+        let unit = u16::from_ne_bytes([bytes[0], bytes[1]]);
+        if E::IS_NATIVE {
+            unit
+        } else {
+            unit.swap_bytes()
+        }
+    }
+}
+
+impl<E: Endian> Encoding for Utf16<E> {
+    const NAME: &'static str = "UTF-16";
+    const UNIT_SIZE: usize = 2;
+
+    fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, EncodingError> {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(EncodingError::Invalid {
+                position: bytes.len() - 1,
+                encoding: Self::NAME,
+            });
+        }
+        let mut string = String::with_capacity(bytes.len() / 2);
+        let mut position = 0;
+        for ch in char::decode_utf16(bytes.chunks_exact(2).map(Self::unit)) {
+            match ch {
+                Ok(ch) => {
+                    string.push(ch);
+                    position += ch.len_utf16() * 2;
+                }
+                Err(_) => {
+                    return Err(EncodingError::Invalid {
+                        position,
+                        encoding: Self::NAME,
+                    });
+                }
+            }
+        }
+        Ok(Cow::Owned(string))
+    }
+
+    fn encode(string: &str) -> Result<Cow<'_, [u8]>, EncodingError> {
+        let mut bytes = Vec::with_capacity(string.len() * 2);
+        for unit in string.encode_utf16() {
+            let unit = if E::IS_NATIVE {
+                unit
+            } else {
+                unit.swap_bytes()
+            };
+            bytes.extend_from_slice(&unit.to_ne_bytes());
+        }
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+// endregion
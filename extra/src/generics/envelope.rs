@@ -0,0 +1,221 @@
+use std::{convert::TryInto, marker::PhantomData};
+
+use represent::{
+    AnalyzeType, AnalyzeWith, MakeType, MakeWith, SizeOverflow, TypeAnalyzer, TypeSize, VisitType,
+    VisitWith,
+};
+
+use super::length::Length;
+use crate::traits::{MakeBlob, MakeWindow, Position};
+
+pub trait RestPolicy {
+    /// leftover bytes of the window are skipped instead of rejected
+    const SKIP: bool;
+}
+
+/// Bytes after the inner value are skipped, so newer writers may append fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SkipRest;
+
+impl RestPolicy for SkipRest {
+    const SKIP: bool = true;
+}
+
+/// The inner value must take up the whole window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyRest;
+
+impl RestPolicy for DenyRest {
+    const SKIP: bool = false;
+}
+
+#[derive(Debug)]
+pub enum SizePrefixedError {
+    Leftover {
+        len: usize,
+        type_name: &'static str,
+    },
+    Overflow {
+        type_name: &'static str,
+    },
+    /// written bytes differ from the analyzed size of the inner value
+    Mismatch {
+        analyzed: usize,
+        written: usize,
+        type_name: &'static str,
+    },
+}
+
+// region: SizePrefixed
+
+/// `T` prefixed by its size in bytes, made inside a window of that size.
+///
+/// Visiting fails if `T` writes a different number of bytes than it's analyzed to.
+#[derive(derivative::Derivative)]
+#[derivative(
+    Debug(bound = "T: std::fmt::Debug"),
+    Clone(bound = "T: Clone"),
+    Default(bound = "T: Default")
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizePrefixed<LEN, T, REST = SkipRest>(
+    pub T,
+    #[derivative(Debug = "ignore")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) PhantomData<(LEN, REST)>,
+);
+
+impl<LEN, T, REST> SizePrefixed<LEN, T, REST> {
+    pub fn new(inner: T) -> Self {
+        Self(inner, PhantomData)
+    }
+}
+
+impl<D, LEN, T, REST> AnalyzeWith<D> for SizePrefixed<LEN, T, REST>
+where
+    D: TypeAnalyzer + AnalyzeType<LEN> + AnalyzeType<T>,
+{
+    const CONST_SIZE: TypeSize = TypeSize::Dynamic;
+
+    represent::expect_sizes!(dynamic D);
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        let header = <D as AnalyzeType<LEN>>::TYPE_CONST_SIZE.expect_const();
+        header
+            .checked_add(analyzer.type_try_dynamic_size(&self.0)?)
+            .ok_or(SizeOverflow)
+    }
+}
+
+impl<M, LEN, T, REST: RestPolicy> MakeWith<M> for SizePrefixed<LEN, T, REST>
+where
+    M: MakeType<LEN> + MakeType<T> + AnalyzeType<Length<LEN>> + MakeWindow + MakeBlob,
+    SizePrefixedError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        let len: LEN = maker.make_type()?;
+        let len = maker.type_dynamic_size(&Length(len));
+        let rest = maker.enter_window(len)?;
+        let res = make_in_window::<M, T, REST>(maker);
+        maker.leave_window(rest);
+        res.map(Self::new)
+    }
+}
+
+fn make_in_window<M, T, REST: RestPolicy>(maker: &mut M) -> Result<T, M::Error>
+where
+    M: MakeType<T> + MakeWindow + MakeBlob,
+    SizePrefixedError: Into<M::Error>,
+{
+    let inner = maker.make_type()?;
+    let left = maker.bytes_left();
+    if left > 0 {
+        if !REST::SKIP {
+            return Err(SizePrefixedError::Leftover {
+                len: left,
+                type_name: std::any::type_name::<T>(),
+            }
+            .into());
+        }
+        maker.skip_bytes(left)?;
+    }
+    Ok(inner)
+}
+
+impl<V, LEN, T, REST> VisitWith<V> for SizePrefixed<LEN, T, REST>
+where
+    V: VisitType<LEN> + VisitType<T> + AnalyzeType<T> + Position,
+    usize: TryInto<LEN, Error: Into<V::Error>>,
+    SizePrefixedError: Into<V::Error>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        let type_name = std::any::type_name::<T>();
+        let analyzed = visitor
+            .type_try_dynamic_size(&self.0)
+            .map_err(|_| SizePrefixedError::Overflow { type_name }.into())?;
+        let len: LEN = analyzed.try_into().map_err(Into::into)?;
+        visitor.visit(&len)?;
+        let start = visitor.position();
+        visitor.visit(&self.0)?;
+        let written = visitor.position() - start;
+        if written != analyzed {
+            return Err(SizePrefixedError::Mismatch {
+                analyzed,
+                written,
+                type_name,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+// endregion
+// region: Skip
+
+/// Steps over `T`, only the number of skipped bytes is kept. Skipped values can't be visited.
+#[derive(derivative::Derivative)]
+#[derivative(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Skip<T>(
+    pub(crate) usize,
+    #[derivative(Debug = "ignore")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) PhantomData<T>,
+);
+
+impl<T> Skip<T> {
+    pub fn skipped(&self) -> usize {
+        self.0
+    }
+}
+
+impl<D, T> AnalyzeWith<D> for Skip<T>
+where
+    D: TypeAnalyzer + AnalyzeType<T>,
+{
+    const CONST_SIZE: TypeSize = D::TYPE_CONST_SIZE;
+
+    fn fixed_size(analyzer: &D) -> usize {
+        D::type_fixed_size(analyzer)
+    }
+
+    fn dynamic_size(&self, _analyzer: &D) -> usize {
+        self.0
+    }
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        D::type_try_fixed_size(analyzer)
+    }
+
+    fn try_dynamic_size(&self, _analyzer: &D) -> Result<usize, SizeOverflow> {
+        Ok(self.0)
+    }
+}
+
+/// Types with `Const` or `Fixed` size are skipped without making them, others are made and dropped.
+impl<M, T> MakeWith<M> for Skip<T>
+where
+    M: MakeType<T> + AnalyzeType<T> + MakeBlob,
+{
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        let skipped = match M::TYPE_CONST_SIZE {
+            TypeSize::Const(size) => {
+                maker.skip_bytes(size)?;
+                size
+            }
+            TypeSize::Fixed => {
+                let size = maker.type_fixed_size();
+                maker.skip_bytes(size)?;
+                size
+            }
+            TypeSize::Dynamic => {
+                let inner: T = maker.make_type()?;
+                maker.type_dynamic_size(&inner)
+            }
+        };
+        Ok(Self(skipped, PhantomData))
+    }
+}
+
+// endregion
@@ -0,0 +1,131 @@
+use represent::{
+    AnalyzeType, AnalyzeWith, MakeWith, SizeOverflow, TypeAnalyzer, TypeSize, VisitWith,
+};
+
+use crate::traits::{MakeBlob, Position, VisitBlob};
+
+#[derive(Debug)]
+pub enum ReservedError {
+    NonZero {
+        offset: usize,
+        value: u8,
+        type_name: &'static str,
+    },
+}
+
+macro_rules! impl_analyze_bytes {
+    ($ty:ident) => {
+        impl<D, const N: usize> AnalyzeWith<D> for $ty<N>
+        where
+            D: TypeAnalyzer + AnalyzeType<[u8; N]>,
+        {
+            const CONST_SIZE: TypeSize = D::TYPE_CONST_SIZE;
+
+            fn fixed_size(analyzer: &D) -> usize {
+                D::type_fixed_size(analyzer)
+            }
+
+            fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+                D::type_try_fixed_size(analyzer)
+            }
+        }
+    };
+}
+
+// region: Pad
+
+/// `N` bytes written as zeros and ignored on make.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pad<const N: usize>;
+
+impl_analyze_bytes!(Pad);
+
+impl<M: MakeBlob, const N: usize> MakeWith<M> for Pad<N> {
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        maker.skip_bytes(N)?;
+        Ok(Self)
+    }
+}
+
+impl<V: VisitBlob, const N: usize> VisitWith<V> for Pad<N> {
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit_raw_blob(&[0u8; N])
+    }
+}
+
+// endregion
+// region: Reserved
+
+/// `N` bytes written as zeros, make fails if any of them is set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reserved<const N: usize>;
+
+impl_analyze_bytes!(Reserved);
+
+impl<M: MakeBlob, const N: usize> MakeWith<M> for Reserved<N>
+where
+    ReservedError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        let bytes = maker.make_raw_blob::<u8>(N)?;
+        match bytes.iter().position(|byte| *byte != 0) {
+            Some(offset) => Err(ReservedError::NonZero {
+                offset,
+                value: bytes[offset],
+                type_name: std::any::type_name::<Self>(),
+            }
+            .into()),
+            None => Ok(Self),
+        }
+    }
+}
+
+impl<V: VisitBlob, const N: usize> VisitWith<V> for Reserved<N> {
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit_raw_blob(&[0u8; N])
+    }
+}
+
+// endregion
+// region: Align
+
+/// Zeros up to the next multiple of `N`, remembers how many were skipped on make.
+///
+/// Its size depends on the offset it's visited at, so it has no `AnalyzeWith` impl and can't be
+/// nested in types that are sized before visiting, like [`super::envelope::SizePrefixed`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Align<const N: usize>(pub(crate) usize);
+
+impl<const N: usize> Align<N> {
+    const ASSERT_NON_ZERO: () = assert!(N > 0, "Align<0> has no multiples");
+
+    #[expect(path_statements)]
+    pub fn padding_at(position: usize) -> usize {
+        Self::ASSERT_NON_ZERO;
+        (N - position % N) % N
+    }
+
+    pub fn padding(&self) -> usize {
+        self.0
+    }
+}
+
+impl<M: MakeBlob + Position, const N: usize> MakeWith<M> for Align<N> {
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        let padding = Self::padding_at(maker.position());
+        maker.skip_bytes(padding)?;
+        Ok(Self(padding))
+    }
+}
+
+impl<V: VisitBlob + Position, const N: usize> VisitWith<V> for Align<N> {
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        let padding = Self::padding_at(visitor.position());
+        visitor.visit_raw_blob(&[0u8; N][..padding])
+    }
+}
+
+// endregion
@@ -4,7 +4,11 @@ use represent::{
     AnalyzeWith, MakeType, MakeWith, Maker, TypeAnalyzer, TypeSize, VisitType, VisitWith, Visitor,
 };
 
-use super::{slots::Slots, Has};
+use super::{
+    limits::{self, DecodeLimits, LimitError},
+    slots::Slots,
+    Has, MaybeHas,
+};
 use crate::traits::{BytesLeft, FixedLength};
 //use crate::{Encrypt, MakeError, Sniffer};
 
@@ -113,12 +117,14 @@ impl<LEN, D: TypeAnalyzer, V> AnalyzeWith<D> for Length<LenMake<LEN, V>> {
 
 impl<M, LEN: Into<u32>, V: Verify<usize>> MakeWith<M> for LenMake<LEN, V>
 where
-    M: Maker + MakeType<LEN>,
+    M: Maker + MakeType<LEN> + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
 {
     fn make_with(maker: &mut M) -> Result<LenMake<LEN, V>, M::Error> {
         let len: LEN = maker.make_type()?;
         let len: u32 = len.into();
         let len = V::verify(len as usize);
+        limits::check_len(maker, len)?;
         Ok(LenMake::new(len))
     }
 }
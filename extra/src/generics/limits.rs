@@ -0,0 +1,146 @@
+use represent::Maker;
+
+use super::MaybeHas;
+
+// region: DecodeLimits
+
+/// Allocation budget of a single decode, checked before allocating for untrusted lengths.
+#[derive(Debug, Clone)]
+pub struct DecodeLimits {
+    pub max_total_bytes: usize,
+    pub max_elements: usize,
+    pub max_depth: usize,
+    allocated: usize,
+    depth: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::new(usize::MAX, usize::MAX, usize::MAX)
+    }
+}
+
+#[derive(Debug)]
+pub enum LimitError {
+    TooManyElements {
+        len: usize,
+        max: usize,
+    },
+    TooManyBytes {
+        requested: usize,
+        allocated: usize,
+        max: usize,
+    },
+    TooDeep {
+        max: usize,
+    },
+}
+
+impl DecodeLimits {
+    pub fn new(max_total_bytes: usize, max_elements: usize, max_depth: usize) -> Self {
+        Self {
+            max_total_bytes,
+            max_elements,
+            max_depth,
+            allocated: 0,
+            depth: 0,
+        }
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.allocated
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn check_len(&self, len: usize) -> Result<(), LimitError> {
+        if len > self.max_elements {
+            Err(LimitError::TooManyElements {
+                len,
+                max: self.max_elements,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Takes `len * element_size` bytes from the budget.
+    pub fn allocate(&mut self, len: usize, element_size: usize) -> Result<(), LimitError> {
+        self.check_len(len)?;
+        let requested = len.checked_mul(element_size);
+        match requested.and_then(|requested| requested.checked_add(self.allocated)) {
+            Some(total) if total <= self.max_total_bytes => {
+                self.allocated = total;
+                Ok(())
+            }
+            _ => Err(LimitError::TooManyBytes {
+                requested: requested.unwrap_or(usize::MAX),
+                allocated: self.allocated,
+                max: self.max_total_bytes,
+            }),
+        }
+    }
+
+    pub fn enter(&mut self) -> Result<(), LimitError> {
+        if self.depth >= self.max_depth {
+            return Err(LimitError::TooDeep {
+                max: self.max_depth,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+// endregion
+
+pub(crate) fn check_len<M>(maker: &M, len: usize) -> Result<(), M::Error>
+where
+    M: Maker + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    match maker.maybe_give() {
+        Some(limits) => limits.check_len(len).map_err(Into::into),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn allocate<M, T>(maker: &mut M, len: usize) -> Result<(), M::Error>
+where
+    M: Maker + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    match maker.maybe_give_mut() {
+        Some(limits) => limits
+            .allocate(len, std::mem::size_of::<T>())
+            .map_err(Into::into),
+        None => Ok(()),
+    }
+}
+
+/// Runs `func` one level deeper, fails with [`LimitError::TooDeep`] past `max_depth`.
+///
+/// Backends can call it from [`Maker::recurse`] to limit recursive types.
+pub fn nested<M, R>(
+    maker: &mut M,
+    func: impl FnOnce(&mut M) -> Result<R, M::Error>,
+) -> Result<R, M::Error>
+where
+    M: Maker + MaybeHas<DecodeLimits>,
+    LimitError: Into<M::Error>,
+{
+    if let Some(limits) = maker.maybe_give_mut() {
+        limits.enter().map_err(Into::into)?;
+    }
+    let res = func(maker);
+    if let Some(limits) = maker.maybe_give_mut() {
+        limits.leave();
+    }
+    res
+}
@@ -0,0 +1,179 @@
+use std::{convert::TryFrom, marker::PhantomData};
+
+use represent::{
+    AnalyzeType, AnalyzeWith, MakeType, MakeWith, SizeOverflow, TypeAnalyzer, TypeSize, VisitType,
+    VisitWith,
+};
+
+/// Packs a byte string into the `BYTES` parameter of [`Magic`], the top byte keeps its length.
+pub const fn magic_bytes(bytes: &[u8]) -> u128 {
+    assert!(bytes.len() < 16, "magic is longer than 15 bytes");
+    let mut value = 0u128;
+    let mut index = 0;
+    while index < bytes.len() {
+        value = (value << 8) | bytes[index] as u128;
+        index += 1;
+    }
+    value | (bytes.len() as u128) << 120
+}
+
+#[derive(Debug)]
+pub enum MagicError {
+    Bytes {
+        expected: Vec<u8>,
+        found: Vec<u8>,
+        type_name: &'static str,
+    },
+    Int {
+        expected: u64,
+        found: u64,
+        type_name: &'static str,
+    },
+}
+
+// region: Magic
+
+/// Byte signature of `N` bytes, `BYTES` must be built with [`magic_bytes`] from `N` bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Magic<const N: usize, const BYTES: u128>;
+
+impl<const N: usize, const BYTES: u128> Magic<N, BYTES> {
+    const ASSERT_LEN: () = assert!(
+        N < 16 && (BYTES >> 120) as usize == N,
+        "magic length doesn't match N"
+    );
+
+    #[expect(path_statements)]
+    pub fn bytes() -> [u8; N] {
+        Self::ASSERT_LEN;
+        let all = BYTES.to_be_bytes();
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&all[all.len() - N..]);
+        bytes
+    }
+}
+
+impl<D, const N: usize, const BYTES: u128> AnalyzeWith<D> for Magic<N, BYTES>
+where
+    D: TypeAnalyzer + AnalyzeType<[u8; N]>,
+{
+    const CONST_SIZE: TypeSize = D::TYPE_CONST_SIZE;
+
+    fn fixed_size(analyzer: &D) -> usize {
+        D::type_fixed_size(analyzer)
+    }
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        D::type_try_fixed_size(analyzer)
+    }
+}
+
+impl<M, const N: usize, const BYTES: u128> MakeWith<M> for Magic<N, BYTES>
+where
+    M: MakeType<[u8; N]>,
+    MagicError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        let found: [u8; N] = maker.make_type()?;
+        let expected = Self::bytes();
+        if found == expected {
+            Ok(Self)
+        } else {
+            Err(MagicError::Bytes {
+                expected: expected.to_vec(),
+                found: found.to_vec(),
+                type_name: std::any::type_name::<Self>(),
+            }
+            .into())
+        }
+    }
+}
+
+impl<V, const N: usize, const BYTES: u128> VisitWith<V> for Magic<N, BYTES>
+where
+    V: VisitType<[u8; N]>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit(&Self::bytes())
+    }
+}
+
+// endregion
+// region: MagicInt
+
+/// Integer signature, stored as `T` in the backend's byte order.
+#[derive(derivative::Derivative)]
+#[derivative(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MagicInt<T, const VALUE: u64>(
+    #[derivative(Debug = "ignore")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    PhantomData<T>,
+);
+
+impl<T: TryFrom<u64> + Into<u64>, const VALUE: u64> MagicInt<T, VALUE> {
+    const ASSERT_FITS: () = assert!(
+        std::mem::size_of::<T>() >= 8 || VALUE >> (8 * std::mem::size_of::<T>()) == 0,
+        "magic doesn't fit into T"
+    );
+
+    #[expect(path_statements)]
+    pub fn value() -> T {
+        Self::ASSERT_FITS;
+        match T::try_from(VALUE) {
+            Ok(value) => value,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl<D, T, const VALUE: u64> AnalyzeWith<D> for MagicInt<T, VALUE>
+where
+    D: TypeAnalyzer + AnalyzeType<T>,
+{
+    const CONST_SIZE: TypeSize = D::TYPE_CONST_SIZE;
+
+    fn fixed_size(analyzer: &D) -> usize {
+        D::type_fixed_size(analyzer)
+    }
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        D::type_try_fixed_size(analyzer)
+    }
+}
+
+impl<M, T, const VALUE: u64> MakeWith<M> for MagicInt<T, VALUE>
+where
+    M: MakeType<T>,
+    T: TryFrom<u64> + Into<u64>,
+    MagicError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        let expected: u64 = Self::value().into();
+        let found: T = maker.make_type()?;
+        let found = found.into();
+        if found == expected {
+            Ok(Self(PhantomData))
+        } else {
+            Err(MagicError::Int {
+                expected: VALUE,
+                found,
+                type_name: std::any::type_name::<Self>(),
+            }
+            .into())
+        }
+    }
+}
+
+impl<V, T, const VALUE: u64> VisitWith<V> for MagicInt<T, VALUE>
+where
+    V: VisitType<T>,
+    T: TryFrom<u64> + Into<u64>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit(&Self::value())
+    }
+}
+
+// endregion
@@ -1,7 +1,14 @@
 pub mod blob;
 pub mod collections;
 pub mod condition;
+pub mod encoding;
+pub mod envelope;
+pub mod layout;
 pub mod length;
+pub mod limits;
+pub mod magic;
+pub mod optional;
+pub mod padding;
 pub mod slots;
 pub mod utility;
 
@@ -0,0 +1,160 @@
+use std::marker::PhantomData;
+
+use represent::{
+    AnalyzeRecursive, AnalyzeType, AnalyzeWith, MakeRecursive, MakeType, MakeWith, Maker,
+    SizeOverflow, TypeAnalyzer, TypeSize, VisitRecursive, VisitType, VisitWith, Visitor,
+};
+
+// region: Optional
+
+#[derive(derivative::Derivative)]
+#[derivative(Debug, Clone(bound = "T: Clone"))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Optional<T, TAG = u8>(
+    pub Option<T>,
+    #[derivative(Debug = "ignore")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) PhantomData<TAG>,
+);
+
+impl<T, TAG> Default for Optional<T, TAG> {
+    fn default() -> Self {
+        Self(Default::default(), Default::default())
+    }
+}
+
+impl<T, TAG> From<Option<T>> for Optional<T, TAG> {
+    fn from(value: Option<T>) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<D, T, TAG> AnalyzeWith<D> for Optional<T, TAG>
+where
+    D: TypeAnalyzer + AnalyzeType<TAG> + AnalyzeType<T>,
+{
+    const CONST_SIZE: TypeSize = TypeSize::Dynamic;
+
+    represent::expect_sizes!(dynamic D);
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        let header = <D as AnalyzeType<TAG>>::TYPE_CONST_SIZE.expect_const();
+        match &self.0 {
+            Some(inner) => header
+                .checked_add(analyzer.type_try_dynamic_size(inner)?)
+                .ok_or(SizeOverflow),
+            None => Ok(header),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum OptionalError {
+    InvalidTag { tag: u32, type_name: &'static str },
+}
+
+impl<M, T, TAG: Into<u32>> MakeWith<M> for Optional<T, TAG>
+where
+    M: MakeType<TAG> + MakeType<T> + Maker,
+    OptionalError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Optional<T, TAG>, M::Error> {
+        let tag: TAG = maker.make_type()?;
+        let inner = match tag.into() {
+            0 => None,
+            1 => Some(maker.make_type()?),
+            tag => {
+                return Err(OptionalError::InvalidTag {
+                    tag,
+                    type_name: std::any::type_name::<Optional<T, TAG>>(),
+                }
+                .into());
+            }
+        };
+        Ok(Optional(inner, Default::default()))
+    }
+}
+
+impl<V, T, TAG: From<bool>> VisitWith<V> for Optional<T, TAG>
+where
+    V: VisitType<TAG> + VisitType<T> + Visitor,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit(&TAG::from(self.0.is_some()))?;
+        match &self.0 {
+            Some(inner) => visitor.visit(inner),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Recursive through the inner container, so `Optional<Box<Self>>` can hold `Self`.
+impl<M, P, T, TAG: Into<u32>> MakeRecursive<M, T> for Optional<P, TAG>
+where
+    M: MakeType<TAG> + Maker,
+    P: MakeRecursive<M, T>,
+    OptionalError: Into<M::Error>,
+{
+    fn make_recursive(
+        maker: &mut M,
+        element: impl FnMut(&mut M) -> Result<T, M::Error>,
+    ) -> Result<Self, M::Error> {
+        let tag: TAG = maker.make_type()?;
+        let inner = match tag.into() {
+            0 => None,
+            1 => Some(P::make_recursive(maker, element)?),
+            tag => {
+                return Err(OptionalError::InvalidTag {
+                    tag,
+                    type_name: std::any::type_name::<Optional<P, TAG>>(),
+                }
+                .into());
+            }
+        };
+        Ok(Optional(inner, Default::default()))
+    }
+}
+
+impl<V, P, T, TAG: From<bool>> VisitRecursive<V, T> for Optional<P, TAG>
+where
+    V: VisitType<TAG> + Visitor,
+    P: VisitRecursive<V, T>,
+{
+    fn visit_recursive(
+        &self,
+        visitor: &mut V,
+        element: impl FnMut(&T, &mut V) -> Result<(), V::Error>,
+    ) -> Result<(), V::Error> {
+        visitor.visit(&TAG::from(self.0.is_some()))?;
+        match &self.0 {
+            Some(inner) => inner.visit_recursive(visitor, element),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<D, P, T, TAG> AnalyzeRecursive<D, T> for Optional<P, TAG>
+where
+    D: TypeAnalyzer + AnalyzeType<TAG>,
+    P: AnalyzeRecursive<D, T>,
+{
+    fn try_recursive_size(
+        &self,
+        analyzer: &D,
+        element: impl FnMut(&T, &D) -> Result<usize, SizeOverflow>,
+    ) -> Result<usize, SizeOverflow> {
+        let header = <D as AnalyzeType<TAG>>::TYPE_CONST_SIZE.expect_const();
+        match &self.0 {
+            Some(inner) => header
+                .checked_add(inner.try_recursive_size(analyzer, element)?)
+                .ok_or(SizeOverflow),
+            None => Ok(header),
+        }
+    }
+}
+
+// endregion
@@ -0,0 +1,185 @@
+use std::{fmt, marker::PhantomData};
+
+use represent::{
+    AnalyzeType, AnalyzeWith, AssertConstOrFixed, MakeType, MakeWith, SizeOverflow, TypeAnalyzer,
+    TypeSize, VisitType, VisitWith,
+};
+
+use super::{
+    blob::BigArr,
+    encoding::{Encoding, EncodingError, Utf8},
+    length::{Length, LengthError},
+};
+
+pub trait Padding {
+    const FILL: char;
+    /// content must be followed by at least one fill char, anything after it is ignored
+    const TERMINATED: bool;
+}
+
+/// Trailing zeros are trimmed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZeroPadded;
+
+impl Padding for ZeroPadded {
+    const FILL: char = '\0';
+    const TERMINATED: bool = false;
+}
+
+/// Trailing spaces are trimmed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpacePadded;
+
+impl Padding for SpacePadded {
+    const FILL: char = ' ';
+    const TERMINATED: bool = false;
+}
+
+/// Content ends at the first zero, a field without zeros is rejected.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NulTerminatedPadded;
+
+impl Padding for NulTerminatedPadded {
+    const FILL: char = '\0';
+    const TERMINATED: bool = true;
+}
+
+// region: PaddedStr
+
+/// Fixed-length string, padding is trimmed on make and restored on visit.
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PaddedStr<LEN, PAD = ZeroPadded, ENC = Utf8>(
+    pub String,
+    #[cfg_attr(feature = "serde", serde(skip))] pub(crate) PhantomData<(LEN, PAD, ENC)>,
+);
+
+impl<LEN, PAD, ENC> PaddedStr<LEN, PAD, ENC> {
+    pub fn new(string: impl Into<String>) -> Self {
+        Self(string.into(), PhantomData)
+    }
+}
+
+impl<LEN, PAD, ENC> fmt::Debug for PaddedStr<LEN, PAD, ENC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<LEN, PAD: Padding, ENC: Encoding> PaddedStr<LEN, PAD, ENC> {
+    fn fill() -> Result<Vec<u8>, EncodingError> {
+        let mut buf = [0; 4];
+        Ok(ENC::encode(PAD::FILL.encode_utf8(&mut buf))?.into_owned())
+    }
+
+    fn trim(bytes: &[u8]) -> Result<&[u8], PaddedStrError> {
+        let fill = Self::fill()?;
+        let mut units = bytes.chunks(fill.len());
+        let end = if PAD::TERMINATED {
+            let index = units
+                .position(|unit| unit == &fill[..])
+                .ok_or(PaddedStrError::Unterminated { len: bytes.len() })?;
+            index * fill.len()
+        } else {
+            let trailing = units.rev().take_while(|unit| *unit == &fill[..]).count();
+            bytes.len() - trailing * fill.len()
+        };
+        Ok(&bytes[..end])
+    }
+
+    fn pad(&self, len: usize) -> Result<Vec<u8>, PaddedStrError> {
+        let fill = Self::fill()?;
+        let mut bytes = ENC::encode(&self.0)?.into_owned();
+        let required = if PAD::TERMINATED {
+            bytes.len() + fill.len()
+        } else {
+            bytes.len()
+        };
+        if required > len || !(len - bytes.len()).is_multiple_of(fill.len()) {
+            return Err(LengthError::Verify {
+                from: required,
+                verified: len,
+            }
+            .into());
+        }
+        while bytes.len() < len {
+            bytes.extend_from_slice(&fill);
+        }
+        Ok(bytes)
+    }
+}
+
+#[derive(Debug)]
+pub enum PaddedStrError {
+    Encoding(EncodingError),
+    Length(LengthError),
+    Unterminated { len: usize },
+}
+
+impl From<EncodingError> for PaddedStrError {
+    fn from(err: EncodingError) -> Self {
+        Self::Encoding(err)
+    }
+}
+
+impl From<LengthError> for PaddedStrError {
+    fn from(err: LengthError) -> Self {
+        Self::Length(err)
+    }
+}
+
+impl<D, LEN, PAD, ENC> AnalyzeWith<D> for PaddedStr<LEN, PAD, ENC>
+where
+    D: TypeAnalyzer + AnalyzeType<BigArr<u8, LEN>>,
+    Length<LEN>: AnalyzeWith<D>,
+{
+    const CONST_SIZE: TypeSize = D::TYPE_CONST_SIZE;
+
+    represent::expect_sizes!(D);
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        AssertConstOrFixed::<D, Length<LEN>>::assert();
+        D::type_try_fixed_size(analyzer)
+    }
+
+    fn try_dynamic_size(&self, analyzer: &D) -> Result<usize, SizeOverflow> {
+        Self::try_fixed_size(analyzer)
+    }
+}
+
+impl<M, LEN, PAD: Padding, ENC: Encoding> MakeWith<M> for PaddedStr<LEN, PAD, ENC>
+where
+    M: MakeType<BigArr<u8, LEN>> + TypeAnalyzer,
+    Length<LEN>: AnalyzeWith<M>,
+    PaddedStrError: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<PaddedStr<LEN, PAD, ENC>, M::Error> {
+        AssertConstOrFixed::<M, Length<LEN>>::assert();
+        let arr: BigArr<u8, LEN> = maker.make_type()?;
+        Self::trim(&arr.0)
+            .and_then(|bytes| Ok(ENC::decode(bytes)?))
+            .map(|string| Self::new(string))
+            .map_err(Into::into)
+    }
+}
+
+impl<V, LEN, PAD: Padding, ENC: Encoding> VisitWith<V> for PaddedStr<LEN, PAD, ENC>
+where
+    V: VisitType<BigArr<u8, LEN>> + TypeAnalyzer,
+    Length<LEN>: AnalyzeWith<V>,
+    PaddedStrError: Into<V::Error>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        AssertConstOrFixed::<V, Length<LEN>>::assert();
+        let len = Length::<LEN>::fixed_size(visitor);
+        let bytes = self.pad(len).map_err(Into::into)?;
+        visitor.visit(&BigArr::new_unchecked(bytes))
+    }
+}
+
+// endregion
@@ -1,7 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
 use represent::{
-    AnalyzeWith, MakeType, MakeWith, Maker, TypeAnalyzer, TypeSize, VisitType, VisitWith, Visitor,
+    AnalyzeWith, MakeType, MakeWith, Maker, SizeOverflow, TypeAnalyzer, TypeSize, VisitType,
+    VisitWith, Visitor,
 };
 
 use super::Has;
@@ -43,6 +44,10 @@ impl<D: TypeAnalyzer, T: AnalyzeWith<D>, const SLOT: usize> AnalyzeWith<D> for S
     fn fixed_size(analyzer: &D) -> usize {
         T::fixed_size(analyzer)
     }
+
+    fn try_fixed_size(analyzer: &D) -> Result<usize, SizeOverflow> {
+        T::try_fixed_size(analyzer)
+    }
 }
 
 impl<M, T, const SLOT: usize> MakeWith<M> for Store<T, SLOT>
@@ -154,3 +159,66 @@ where
         Ok(())
     }
 }
+
+#[derive(Debug)]
+pub enum SlotTagError {
+    Mismatch { slot: usize, stored: u32, tag: u32 },
+}
+
+/// Tag of an `#[alt(via = "SlotTag<T, SLOT>")]` enum, made as a [`Load`], stored or checked
+/// against `SLOT` on visit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SlotTag<T, const SLOT: usize>(pub T);
+
+macro_rules! impl_slot_tag_from {
+    ($($ty:ty),*) => {$(
+        impl<const SLOT: usize> From<$ty> for SlotTag<$ty, SLOT> {
+            fn from(tag: $ty) -> Self {
+                SlotTag(tag)
+            }
+        }
+
+        impl<const SLOT: usize> From<SlotTag<$ty, SLOT>> for $ty {
+            fn from(tag: SlotTag<$ty, SLOT>) -> Self {
+                tag.0
+            }
+        }
+    )*};
+}
+
+impl_slot_tag_from!(u8, u16, u32);
+
+impl<D: TypeAnalyzer, T, const SLOT: usize> AnalyzeWith<D> for SlotTag<T, SLOT> {
+    const CONST_SIZE: TypeSize = TypeSize::Const(0);
+}
+
+impl<M, T, const SLOT: usize> MakeWith<M> for SlotTag<T, SLOT>
+where
+    M: Maker + Has<Slots>,
+    T: TryFrom<u32>,
+    SlotLoadError<<T as TryFrom<u32>>::Error>: Into<M::Error>,
+{
+    fn make_with(maker: &mut M) -> Result<Self, M::Error> {
+        Load::<T, SLOT>::make_with(maker).map(|Load(tag)| SlotTag(tag))
+    }
+}
+
+impl<V, T, const SLOT: usize> VisitWith<V> for SlotTag<T, SLOT>
+where
+    V: Visitor + Has<Slots>,
+    T: Into<u32> + Clone,
+    SlotTagError: Into<V::Error>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        let slots: &mut Slots = visitor.give_mut();
+        let tag = self.0.clone().into();
+        slots.store(SLOT, tag).map_err(|stored| {
+            SlotTagError::Mismatch {
+                slot: SLOT,
+                stored,
+                tag,
+            }
+            .into()
+        })
+    }
+}
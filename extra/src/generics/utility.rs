@@ -1,6 +1,6 @@
 use std::fmt;
 
-use represent::{AnalyzeWith, MakeType, MakeWith, Maker, TypeAnalyzer, TypeSize};
+use represent::{AnalyzeWith, MakeType, MakeWith, Maker, SizeOverflow, TypeAnalyzer, TypeSize};
 
 use super::condition::Condition;
 //use crate::{Encrypt, MakeError, Sniffer};
@@ -34,6 +34,10 @@ impl<A: TypeAnalyzer, T: AnalyzeWith<A>> AnalyzeWith<A> for Dbg<T> {
     fn fixed_size(analyzer: &A) -> usize {
         T::fixed_size(analyzer)
     }
+
+    fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+        T::try_fixed_size(analyzer)
+    }
 }
 
 impl<M: Maker + TypeAnalyzer + MakeType<T>, T: fmt::Debug> MakeWith<M> for Dbg<T> {
@@ -1,3 +1,4 @@
+pub mod endian;
 pub mod generics;
 mod macros;
 pub mod traits;
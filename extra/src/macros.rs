@@ -64,6 +64,14 @@ macro_rules! impl_analyzer (
             fn type_dynamic_size(&self, target: &T) -> usize {
                 target.dynamic_size(self)
             }
+
+            fn type_try_fixed_size(&self) -> Result<usize, represent::SizeOverflow> {
+                T::try_fixed_size(self)
+            }
+
+            fn type_try_dynamic_size(&self, target: &T) -> Result<usize, represent::SizeOverflow> {
+                target.try_dynamic_size(self)
+            }
         }
     }
 );
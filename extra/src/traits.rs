@@ -1,12 +1,62 @@
 use represent::{Maker, Visitor};
 
+use crate::endian::{BlobElement, Endian};
+
 pub trait MakeBlob: Maker {
-    fn make_blob<T: bytemuck::Pod>(&mut self, len: usize) -> Result<Vec<T>, Self::Error>;
+    /// byte order of multi-byte elements on the wire
+    type Endian: Endian;
+
+    /// elements are returned in wire byte order
+    fn make_raw_blob<T: bytemuck::Pod>(&mut self, len: usize) -> Result<Vec<T>, Self::Error>;
+
+    fn make_blob<T: BlobElement<Self::Endian>>(
+        &mut self,
+        len: usize,
+    ) -> Result<Vec<T>, Self::Error> {
+        let mut blob = self.make_raw_blob(len)?;
+        T::swap_to_native(&mut blob);
+        Ok(blob)
+    }
+
+    /// advances past `len` bytes, backends that can seek should override it
+    fn skip_bytes(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.make_raw_blob::<u8>(len).map(drop)
+    }
+
+    /// bytes before the next `end` byte, which is consumed too; `None` if it isn't within the
+    /// next `max + 1` bytes. Reads byte by byte, backends with buffered input should override it
+    fn make_raw_until(&mut self, end: u8, max: usize) -> Result<Option<Vec<u8>>, Self::Error> {
+        let mut vec = Vec::new();
+        loop {
+            let byte = self.make_raw_blob::<u8>(1)?[0];
+            if byte == end {
+                return Ok(Some(vec));
+            }
+            if vec.len() == max {
+                return Ok(None);
+            }
+            vec.push(byte);
+        }
+    }
 }
 
 pub trait VisitBlob: Visitor {
+    /// byte order of multi-byte elements on the wire
+    type Endian: Endian;
+
+    /// length of the blob is already visited, elements are expected in wire byte order
+    fn visit_raw_blob<T: bytemuck::Pod>(&mut self, blob: &[T]) -> Result<(), Self::Error>;
+
     /// length of the blob is already visited
-    fn visit_blob<T: bytemuck::Pod>(&mut self, blob: &[T]) -> Result<(), Self::Error>;
+    fn visit_blob<T: BlobElement<Self::Endian>>(&mut self, blob: &[T]) -> Result<(), Self::Error> {
+        if Self::Endian::IS_NATIVE || std::mem::size_of::<T>() < 2 {
+            self.visit_raw_blob(blob)
+        } else {
+            let mut swapped = blob.to_vec();
+            T::swap_to_native(&mut swapped);
+            self.visit_raw_blob(&swapped)
+        }
+    }
 }
 
 pub trait BytesLeft {
@@ -16,3 +66,16 @@ pub trait BytesLeft {
 pub trait FixedLength<A> {
     fn fixed_length(analyzer: &A) -> usize;
 }
+
+/// Offset of the next byte, counted from the start of the stream.
+pub trait Position {
+    fn position(&self) -> usize;
+}
+
+/// Restricts [`BytesLeft`] to a sub-range of the input.
+pub trait MakeWindow: Maker + BytesLeft {
+    /// limits `bytes_left` to the next `len` bytes, returns how many bytes follow the window
+    fn enter_window(&mut self, len: usize) -> Result<usize, Self::Error>;
+    /// `rest` is the value returned by the matching `enter_window`
+    fn leave_window(&mut self, rest: usize);
+}
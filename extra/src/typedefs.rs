@@ -1,15 +1,31 @@
+use std::collections::HashMap;
+
 use crate::generics::{
-    blob::{BigArr, BigStr},
-    collections::RepeatExt,
+    blob::{Arr, BigArr, BigStr},
+    collections::{Bulk, MapExt, RepeatExt},
+    encoding::Utf8,
     length::{LenConst, LenMake, LenRest, LenSlot},
+    padding::{PaddedStr, ZeroPadded},
 };
 
 pub type RepeatMake<L, T> = RepeatExt<T, LenMake<L>>;
 pub type RepeatSlot<T, const SLOT: usize> = RepeatExt<T, LenSlot<(), SLOT>>;
+pub type RepeatBulk<L, T> = RepeatExt<T, LenMake<L>, Bulk>;
+
+pub type MapMake<L, K, V> = MapExt<K, V, LenMake<L>>;
+pub type MapSlot<K, V, const SLOT: usize> = MapExt<K, V, LenSlot<(), SLOT>>;
+pub type HashMapMake<L, K, V> = MapExt<K, V, LenMake<L>, HashMap<K, V>>;
 
 pub type BigArrMake<L, T> = BigArr<T, LenMake<L>>;
 pub type BigStaticArr<T, const LEN: usize> = BigArr<T, LenConst<LEN>>;
 pub type BigArrSlot<T, const SLOT: usize> = BigArr<T, LenSlot<(), SLOT>>;
 pub type TailBytes = BigArr<u8, LenRest>;
 
-pub type StaticStr<const LEN: usize> = BigStr<LenConst<LEN>>;
+pub type ArrMake<L, T> = Arr<T, LenMake<L>>;
+pub type StaticArr<T, const LEN: usize> = Arr<T, LenConst<LEN>>;
+pub type ArrSlot<T, const SLOT: usize> = Arr<T, LenSlot<(), SLOT>>;
+
+pub type BigStrMake<L, ENC = Utf8> = BigStr<LenMake<L>, ENC>;
+pub type StaticStr<const LEN: usize, ENC = Utf8> = BigStr<LenConst<LEN>, ENC>;
+pub type PaddedStaticStr<const LEN: usize, PAD = ZeroPadded, ENC = Utf8> =
+    PaddedStr<LenConst<LEN>, PAD, ENC>;
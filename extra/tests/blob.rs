@@ -0,0 +1,85 @@
+mod common;
+
+use common::{Reader, read, read_be, write, write_be};
+use represent::{AnalyzeWith, SizeOverflow, checked_repeat_size};
+use represent_extra::{
+    endian::{BigEndian, Endian, LittleEndian},
+    generics::blob::BigArr,
+    typedefs::{ArrMake, BigArrMake, RepeatBulk},
+};
+
+#[test]
+fn big_arr_roundtrip() {
+    let bytes = [2, 0x01, 0x02, 0x03, 0x04];
+    let arr: BigArrMake<u8, u16> = read(bytes).unwrap();
+    assert_eq!(arr.0, [0x0201, 0x0403]);
+    assert_eq!(write(&arr).unwrap(), bytes);
+}
+
+#[test]
+fn arr_of_non_pod_roundtrip() {
+    let bytes = [2, 1, 0x02, 0x00, 3, 0x04, 0x00];
+    let arr: ArrMake<u8, (u8, u16)> = read(bytes).unwrap();
+    assert_eq!(arr.0, [(1, 2), (3, 4)]);
+    assert_eq!(write(&arr).unwrap(), bytes);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Point {
+    x: u16,
+    y: u16,
+}
+
+#[test]
+fn big_arr_of_pod_structs() {
+    let bytes = [1, 0x01, 0x00, 0x02, 0x00];
+    let arr: BigArrMake<u8, Point> = read(bytes).unwrap();
+    if cfg!(target_endian = "little") {
+        assert_eq!(arr.0, [Point { x: 1, y: 2 }]);
+    }
+    assert_eq!(write(&arr).unwrap(), bytes);
+
+    let bytes = [2, b'a', b'b', b'c', b'd'];
+    let arr: BigArrMake<u8, [u8; 2]> = read_be(bytes).unwrap();
+    assert_eq!(arr.0, [*b"ab", *b"cd"]);
+    assert_eq!(write_be(&arr).unwrap(), bytes);
+}
+
+#[test]
+fn big_endian_swap() {
+    let mut blob = [0x0102u16, 0x0304];
+    BigEndian::swap_to_native(&mut blob);
+    if cfg!(target_endian = "little") {
+        assert_eq!(blob, [0x0201, 0x0403]);
+    } else {
+        assert_eq!(blob, [0x0102, 0x0304]);
+    }
+
+    let mut blob = [0x01020304u32];
+    LittleEndian::swap_to_native(&mut blob);
+    BigEndian::swap_to_native(&mut blob);
+    BigEndian::swap_to_native(&mut blob);
+    assert_eq!(blob, [0x01020304]);
+}
+
+#[test]
+fn big_endian_backend() {
+    let bytes = [0x00, 2, 0x01, 0x02, 0x03, 0x04];
+    let arr: BigArrMake<u16, u16> = read_be(bytes).unwrap();
+    assert_eq!(arr.0, [0x0102, 0x0304]);
+    assert_eq!(write_be(&arr).unwrap(), bytes);
+
+    let bytes = [1, 0x01, 0x02, 0x03, 0x04];
+    let bulk: RepeatBulk<u8, u32> = read_be(bytes).unwrap();
+    assert_eq!(bulk.0, [0x01020304]);
+    assert_eq!(write_be(&bulk).unwrap(), bytes);
+}
+
+#[test]
+fn size_overflow() {
+    let analyzer = Reader::default();
+    let arr: BigArrMake<u8, u16> = BigArr::new_unchecked(vec![0; 4]);
+    assert_eq!(arr.try_dynamic_size(&analyzer), Ok(9));
+    assert_eq!(checked_repeat_size(2, usize::MAX / 2, 4), Err(SizeOverflow));
+}
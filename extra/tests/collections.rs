@@ -0,0 +1,63 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{Error, read, write};
+use represent_extra::{
+    generics::{
+        collections::MapError,
+        optional::{Optional, OptionalError},
+    },
+    typedefs::{HashMapMake, MapMake, RepeatBulk, RepeatMake},
+};
+
+#[test]
+fn map_roundtrip_in_key_order() {
+    let bytes = [2, 7, 0x70, 0x00, 3, 0x30, 0x00];
+    let map: MapMake<u8, u8, u16> = read(bytes).unwrap();
+    assert_eq!(map.0, BTreeMap::from([(3, 0x30), (7, 0x70)]));
+    assert_eq!(write(&map).unwrap(), [2, 3, 0x30, 0x00, 7, 0x70, 0x00]);
+
+    let map: HashMapMake<u8, u8, u16> = read(bytes).unwrap();
+    assert_eq!(write(&map).unwrap(), [2, 3, 0x30, 0x00, 7, 0x70, 0x00]);
+}
+
+#[test]
+fn map_duplicate_key() {
+    let res: Result<MapMake<u8, u8, u8>, _> = read([2, 1, 1, 1, 2]);
+    assert!(matches!(
+        res,
+        Err(Error::Map(MapError::DuplicateKey { index: 1, .. }))
+    ));
+}
+
+#[test]
+fn repeat_bulk_roundtrip() {
+    let bytes = [3, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00];
+    let bulk: RepeatBulk<u8, u16> = read(bytes).unwrap();
+    assert_eq!(bulk.0, [1, 2, 3]);
+    assert_eq!(write(&bulk).unwrap(), bytes);
+
+    let per_element: RepeatMake<u8, u16> = read(bytes).unwrap();
+    assert_eq!(per_element.0, bulk.0);
+}
+
+#[test]
+fn optional_roundtrip() {
+    let absent: Optional<u16> = read([0]).unwrap();
+    assert_eq!(absent.0, None);
+    assert_eq!(write(&absent).unwrap(), [0]);
+
+    let present: Optional<u16> = read([1, 0x02, 0x01]).unwrap();
+    assert_eq!(present.0, Some(0x0102));
+    assert_eq!(write(&present).unwrap(), [1, 0x02, 0x01]);
+}
+
+#[test]
+fn optional_invalid_tag() {
+    let res: Result<Optional<u16>, _> = read([2, 0x02, 0x01]);
+    assert!(matches!(
+        res,
+        Err(Error::Optional(OptionalError::InvalidTag { tag: 2, .. }))
+    ));
+}
@@ -0,0 +1,286 @@
+#![allow(dead_code)]
+
+use std::{convert::TryInto, marker::PhantomData, num::TryFromIntError};
+
+use represent::{
+    AnalyzeWith, MakeType, MakeWith, Maker, TypeSize, UnknownDiscriminant, VisitType, VisitWith,
+    Visitor,
+};
+use represent_extra::{
+    endian::{BigEndian, Endian, LittleEndian},
+    generics::{
+        HasValue, MaybeHasValue,
+        blob::CStrError,
+        collections::MapError,
+        envelope::SizePrefixedError,
+        layout::ReservedError,
+        length::LengthError,
+        limits::{self, DecodeLimits, LimitError},
+        magic::MagicError,
+        optional::OptionalError,
+        padding::PaddedStrError,
+        slots::{SlotLoadError, SlotTagError, Slots},
+    },
+    traits::{BytesLeft, MakeBlob, MakeWindow, Position, VisitBlob},
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Eof,
+    CStr(CStrError),
+    Discriminant(UnknownDiscriminant),
+    Length(LengthError),
+    Limit(LimitError),
+    Magic(MagicError),
+    Map(MapError),
+    Optional(OptionalError),
+    PaddedStr(PaddedStrError),
+    Reserved(ReservedError),
+    SizePrefixed(SizePrefixedError),
+    SlotLoad(SlotLoadError<TryFromIntError>),
+    SlotTag(SlotTagError),
+}
+
+macro_rules! impl_from_error {
+    ($($variant:ident($ty:ty)),*) => {$(
+        impl From<$ty> for Error {
+            fn from(err: $ty) -> Self {
+                Error::$variant(err)
+            }
+        }
+    )*};
+}
+
+impl_from_error!(
+    CStr(CStrError),
+    Discriminant(UnknownDiscriminant),
+    Length(LengthError),
+    Limit(LimitError),
+    Magic(MagicError),
+    Map(MapError),
+    Optional(OptionalError),
+    PaddedStr(PaddedStrError),
+    Reserved(ReservedError),
+    SizePrefixed(SizePrefixedError),
+    SlotLoad(SlotLoadError<TryFromIntError>),
+    SlotTag(SlotTagError)
+);
+
+/// Reader over a byte buffer in `E` byte order.
+#[derive(Debug, Default)]
+pub struct ByteReader<E> {
+    pub buf: Vec<u8>,
+    pub pos: usize,
+    pub slots: Slots,
+    pub limits: Option<DecodeLimits>,
+    pub end: Option<usize>,
+    endian: PhantomData<E>,
+}
+
+pub type Reader = ByteReader<LittleEndian>;
+pub type BigEndianReader = ByteReader<BigEndian>;
+
+impl<E: Default> ByteReader<E> {
+    pub fn new(buf: impl Into<Vec<u8>>) -> Self {
+        Self {
+            buf: buf.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<E> ByteReader<E> {
+    fn take(&mut self, len: usize) -> Result<&[u8], Error> {
+        if self.bytes_left() < len {
+            return Err(Error::Eof);
+        }
+        let slice = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+}
+
+/// Writer into a byte buffer in `E` byte order.
+#[derive(Debug, Default)]
+pub struct ByteWriter<E> {
+    pub buf: Vec<u8>,
+    pub slots: Slots,
+    endian: PhantomData<E>,
+}
+
+pub type Writer = ByteWriter<LittleEndian>;
+pub type BigEndianWriter = ByteWriter<BigEndian>;
+
+represent_extra::impl_analyzer!([E] for ByteReader<E>);
+represent_extra::impl_analyzer!([E] for ByteWriter<E>);
+
+impl<E> Maker for ByteReader<E> {
+    type Error = Error;
+
+    fn recurse<R>(&mut self, func: impl FnOnce(&mut Self) -> Result<R, Error>) -> Result<R, Error> {
+        limits::nested(self, func)
+    }
+}
+
+impl<E> Visitor for ByteWriter<E> {
+    type Error = Error;
+}
+
+impl<E> HasValue<Slots> for ByteReader<E> {
+    type Value = Slots;
+
+    fn give_value(&self) -> &Slots {
+        &self.slots
+    }
+
+    fn give_value_mut(&mut self) -> &mut Slots {
+        &mut self.slots
+    }
+}
+
+impl<E> MaybeHasValue<DecodeLimits> for ByteReader<E> {
+    type Value = DecodeLimits;
+
+    fn maybe_give_value(&self) -> Option<&DecodeLimits> {
+        self.limits.as_ref()
+    }
+
+    fn maybe_give_value_mut(&mut self) -> Option<&mut DecodeLimits> {
+        self.limits.as_mut()
+    }
+
+    fn maybe_replace_value(&mut self, other: DecodeLimits) -> Option<DecodeLimits> {
+        self.limits.replace(other)
+    }
+}
+
+impl<E> HasValue<Slots> for ByteWriter<E> {
+    type Value = Slots;
+
+    fn give_value(&self) -> &Slots {
+        &self.slots
+    }
+
+    fn give_value_mut(&mut self) -> &mut Slots {
+        &mut self.slots
+    }
+}
+
+impl<E> BytesLeft for ByteReader<E> {
+    fn bytes_left(&self) -> usize {
+        self.end.unwrap_or(self.buf.len()) - self.pos
+    }
+}
+
+impl<E> MakeWindow for ByteReader<E> {
+    fn enter_window(&mut self, len: usize) -> Result<usize, Error> {
+        let left = self.bytes_left();
+        if left < len {
+            return Err(Error::Eof);
+        }
+        self.end = Some(self.pos + len);
+        Ok(left - len)
+    }
+
+    fn leave_window(&mut self, rest: usize) {
+        self.end = Some(self.pos + self.bytes_left() + rest);
+    }
+}
+
+impl<E> Position for ByteReader<E> {
+    fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<E> Position for ByteWriter<E> {
+    fn position(&self) -> usize {
+        self.buf.len()
+    }
+}
+
+impl<E: Endian> MakeBlob for ByteReader<E> {
+    type Endian = E;
+
+    fn make_raw_blob<T: bytemuck::Pod>(&mut self, len: usize) -> Result<Vec<T>, Error> {
+        let bytes = self.take(len * std::mem::size_of::<T>())?;
+        let mut vec = vec![T::zeroed(); len];
+        bytemuck::cast_slice_mut(&mut vec).copy_from_slice(bytes);
+        Ok(vec)
+    }
+}
+
+impl<E: Endian> VisitBlob for ByteWriter<E> {
+    type Endian = E;
+
+    fn visit_raw_blob<T: bytemuck::Pod>(&mut self, blob: &[T]) -> Result<(), Error> {
+        self.buf.extend_from_slice(bytemuck::cast_slice(blob));
+        Ok(())
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {$(
+        impl<E: Endian> MakeType<$ty> for ByteReader<E> {
+            fn make_type(&mut self) -> Result<$ty, Error> {
+                let bytes = self.take(std::mem::size_of::<$ty>())?;
+                let mut value = [<$ty>::from_ne_bytes(bytes.try_into().unwrap())];
+                E::swap_to_native(&mut value);
+                Ok(value[0])
+            }
+        }
+
+        impl<E: Endian> VisitType<$ty> for ByteWriter<E> {
+            fn visit(&mut self, target: &$ty) -> Result<(), Error> {
+                let mut value = [*target];
+                E::swap_to_native(&mut value);
+                self.buf.extend_from_slice(&value[0].to_ne_bytes());
+                Ok(())
+            }
+        }
+
+        impl<E> AnalyzeWith<ByteReader<E>> for $ty {
+            const CONST_SIZE: TypeSize = TypeSize::Const(std::mem::size_of::<$ty>());
+        }
+
+        impl<E> AnalyzeWith<ByteWriter<E>> for $ty {
+            const CONST_SIZE: TypeSize = TypeSize::Const(std::mem::size_of::<$ty>());
+        }
+    )*};
+}
+
+impl_primitive!(u8, u16, u32);
+
+impl<E, T: MakeWith<Self>> MakeType<T> for ByteReader<E> {
+    fn make_type(&mut self) -> Result<T, Error> {
+        T::make_with(self)
+    }
+}
+
+impl<E, T: VisitWith<Self>> VisitType<T> for ByteWriter<E> {
+    fn visit(&mut self, target: &T) -> Result<(), Error> {
+        target.visit_with(self)
+    }
+}
+
+pub fn write<T: VisitWith<Writer>>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut writer = Writer::default();
+    writer.visit(value)?;
+    Ok(writer.buf)
+}
+
+pub fn read<T: MakeWith<Reader>>(bytes: impl Into<Vec<u8>>) -> Result<T, Error> {
+    let mut reader = Reader::new(bytes);
+    reader.make()
+}
+
+pub fn write_be<T: VisitWith<BigEndianWriter>>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut writer = BigEndianWriter::default();
+    writer.visit(value)?;
+    Ok(writer.buf)
+}
+
+pub fn read_be<T: MakeWith<BigEndianReader>>(bytes: impl Into<Vec<u8>>) -> Result<T, Error> {
+    let mut reader = BigEndianReader::new(bytes);
+    reader.make()
+}
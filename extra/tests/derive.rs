@@ -0,0 +1,113 @@
+mod common;
+
+use common::{Error, Reader, read, write};
+use represent::{AnalyzeType, Maker, TypeSize, UnknownDiscriminant};
+use represent_derive::{AnalyzeWith, MakeWith, VisitWith};
+use represent_extra::{
+    generics::slots::{SlotLoadError, SlotTag, SlotTagError, Store},
+    typedefs::TailBytes,
+};
+
+#[derive(Debug, MakeWith, VisitWith, AnalyzeWith)]
+#[alt(ty = "u8")]
+enum Message {
+    #[alt("1")]
+    Ping(u16),
+    #[alt(other)]
+    Unknown { tag: u8, body: TailBytes },
+}
+
+#[test]
+fn unknown_message_is_forwarded() {
+    let bytes = [9, 1, 2, 3];
+    let mut reader = Reader::new(bytes);
+    let message: Message = reader.make().unwrap();
+    match &message {
+        Message::Unknown { tag, body } => {
+            assert_eq!(*tag, 9);
+            assert_eq!(body.0, [1, 2, 3]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(reader.type_dynamic_size(&message), bytes.len());
+    assert_eq!(write(&message).unwrap(), bytes);
+
+    let message: Message = read([1, 0x34, 0x12]).unwrap();
+    assert!(matches!(message, Message::Ping(0x1234)));
+}
+
+#[derive(Debug, MakeWith, VisitWith, AnalyzeWith, PartialEq)]
+#[alt(ty = "u8", via = "SlotTag<u8, 0>")]
+enum Body {
+    #[alt("1")]
+    Ping(u16),
+    #[alt("2")]
+    Pong,
+    #[alt(other)]
+    Unknown(u8),
+}
+
+#[derive(Debug, MakeWith, VisitWith)]
+struct Frame {
+    opcode: Store<u8, 0>,
+    body: Body,
+}
+
+#[test]
+fn alt_tag_from_slot() {
+    let bytes = [1, 0x34, 0x12];
+    let frame: Frame = read(bytes).unwrap();
+    assert_eq!(frame.opcode.inner, 1);
+    assert_eq!(frame.body, Body::Ping(0x1234));
+    assert_eq!(write(&frame).unwrap(), bytes);
+    assert_eq!(Reader::default().type_dynamic_size(&Body::Pong), 0);
+
+    let frame = Frame {
+        opcode: Store { inner: 1 },
+        body: Body::Pong,
+    };
+    assert!(matches!(
+        write(&frame),
+        Err(Error::SlotTag(SlotTagError::Mismatch {
+            slot: 0,
+            stored: 1,
+            tag: 2
+        }))
+    ));
+
+    let frame: Frame = read([7]).unwrap();
+    assert_eq!(frame.body, Body::Unknown(7));
+    assert_eq!(write(&frame).unwrap(), [7]);
+
+    let res: Result<Body, _> = read([1, 0x34, 0x12]);
+    assert!(matches!(
+        res,
+        Err(Error::SlotLoad(SlotLoadError::EmptySlot))
+    ));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, MakeWith, VisitWith, AnalyzeWith)]
+#[repr(u16)]
+enum Kind {
+    Data = 1,
+    Ack = 5,
+    Nack,
+}
+
+#[test]
+fn repr_enum_discriminants() {
+    let kind: Kind = read([6, 0]).unwrap();
+    assert_eq!(kind, Kind::Nack);
+    assert_eq!(write(&Kind::Ack).unwrap(), [5, 0]);
+    assert_eq!(write(&Kind::Data).unwrap(), [1, 0]);
+    assert!(matches!(
+        <Reader as AnalyzeType<Kind>>::TYPE_CONST_SIZE,
+        TypeSize::Const(2)
+    ));
+
+    let res: Result<Kind, _> = read([2, 0]);
+    assert!(matches!(
+        res,
+        Err(Error::Discriminant(UnknownDiscriminant { value: 2, .. }))
+    ));
+}
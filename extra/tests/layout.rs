@@ -0,0 +1,146 @@
+mod common;
+
+use common::{Error, Reader, read, write};
+use represent::{AnalyzeType, TypeSize};
+use represent_extra::generics::{
+    blob::BigArr,
+    envelope::{DenyRest, SizePrefixed, SizePrefixedError, Skip, SkipRest},
+    layout::{Align, Pad, Reserved, ReservedError},
+    length::LenMake,
+    magic::{Magic, MagicError, MagicInt, magic_bytes},
+};
+
+type Elf = Magic<4, { magic_bytes(b"\x7fELF") }>;
+type Png = MagicInt<u32, 0x474e_5089>;
+
+#[test]
+fn magic_roundtrip() {
+    let elf: Elf = read(*b"\x7fELF").unwrap();
+    assert_eq!(write(&elf).unwrap(), b"\x7fELF");
+    let png: Png = read([0x89, b'P', b'N', b'G']).unwrap();
+    assert_eq!(write(&png).unwrap(), [0x89, b'P', b'N', b'G']);
+
+    assert!(matches!(
+        <Reader as AnalyzeType<Elf>>::TYPE_CONST_SIZE,
+        TypeSize::Const(4)
+    ));
+    assert!(matches!(
+        <Reader as AnalyzeType<Png>>::TYPE_CONST_SIZE,
+        TypeSize::Const(4)
+    ));
+}
+
+#[test]
+fn magic_mismatch() {
+    let res: Result<Elf, _> = read(*b"\x7fELL");
+    match res {
+        Err(Error::Magic(MagicError::Bytes {
+            expected, found, ..
+        })) => {
+            assert_eq!(expected, b"\x7fELF");
+            assert_eq!(found, b"\x7fELL");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    let res: Result<Png, _> = read(*b"GIF8");
+    assert!(matches!(
+        res,
+        Err(Error::Magic(MagicError::Int {
+            expected: 0x474e_5089,
+            found: 0x3846_4947,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn pad_and_reserved() {
+    let (a, _, b): (u8, Pad<3>, u8) = read([1, 7, 7, 7, 2]).unwrap();
+    assert_eq!((a, b), (1, 2));
+    assert_eq!(write(&(a, Pad::<3>, b)).unwrap(), [1, 0, 0, 0, 2]);
+
+    let (a, _, b): (u8, Reserved<2>, u8) = read([1, 0, 0, 2]).unwrap();
+    assert_eq!(write(&(a, Reserved::<2>, b)).unwrap(), [1, 0, 0, 2]);
+    let res: Result<(u8, Reserved<2>, u8), _> = read([1, 0, 9, 2]);
+    assert!(matches!(
+        res,
+        Err(Error::Reserved(ReservedError::NonZero {
+            offset: 1,
+            value: 9,
+            ..
+        }))
+    ));
+
+    assert!(matches!(
+        <Reader as AnalyzeType<Pad<3>>>::TYPE_CONST_SIZE,
+        TypeSize::Const(3)
+    ));
+}
+
+#[test]
+fn align_to_position() {
+    let value: (u8, Align<4>, u32) = read([1, 9, 9, 9, 2, 0, 0, 0]).unwrap();
+    assert_eq!(value.1.padding(), 3);
+    assert_eq!(value.2, 2);
+    assert_eq!(write(&value).unwrap(), [1, 0, 0, 0, 2, 0, 0, 0]);
+
+    let value: (u32, Align<4>, u8) = read([1, 0, 0, 0, 2]).unwrap();
+    assert_eq!(value.1.padding(), 0);
+    assert_eq!(write(&value).unwrap(), [1, 0, 0, 0, 2]);
+}
+
+type Envelope<REST = SkipRest> = SizePrefixed<LenMake<u8>, (u8, u16), REST>;
+
+#[test]
+fn size_prefixed_roundtrip() {
+    let value: (Envelope, u8) = read([3, 1, 2, 0, 9]).unwrap();
+    assert_eq!((value.0).0, (1, 2));
+    assert_eq!(value.1, 9);
+    assert_eq!(write(&value).unwrap(), [3, 1, 2, 0, 9]);
+
+    let value: (Envelope, u8) = read([5, 1, 2, 0, 7, 7, 9]).unwrap();
+    assert_eq!((value.0).0, (1, 2));
+    assert_eq!(value.1, 9);
+}
+
+#[test]
+fn align_after_size_prefixed() {
+    let bytes = [3, 1, 2, 0, 7, 0, 0, 0];
+    let value: (Envelope, Align<4>, u32) = read(bytes).unwrap();
+    assert_eq!(value.1.padding(), 0);
+    assert_eq!(value.2, 7);
+    assert_eq!(write(&value).unwrap(), bytes);
+
+    let value: (Envelope, Align<4>, u32) = read([5, 1, 2, 0, 9, 9, 8, 8, 7, 0, 0, 0]).unwrap();
+    assert_eq!(value.1.padding(), 2);
+    assert_eq!(value.2, 7);
+    assert_eq!(write(&value).unwrap(), bytes);
+}
+
+#[test]
+fn size_prefixed_window() {
+    let res: Result<(Envelope<DenyRest>, u8), _> = read([5, 1, 2, 0, 7, 7, 9]);
+    assert!(matches!(
+        res,
+        Err(Error::SizePrefixed(SizePrefixedError::Leftover {
+            len: 2,
+            ..
+        }))
+    ));
+    let res: Result<(Envelope<DenyRest>, u8), _> = read([2, 1, 2, 0, 9]);
+    assert!(matches!(res, Err(Error::Eof)));
+}
+
+#[test]
+fn skip_by_size() {
+    let value: (Skip<(u16, u32)>, u8) = read([1, 1, 2, 2, 2, 2, 9]).unwrap();
+    assert_eq!(value.0.skipped(), 6);
+    assert_eq!(value.1, 9);
+
+    let value: (Skip<BigArr<u16, LenMake<u8>>>, u8) = read([2, 1, 1, 2, 2, 9]).unwrap();
+    assert_eq!(value.0.skipped(), 5);
+    assert_eq!(value.1, 9);
+
+    let res: Result<Skip<u32>, _> = read([1, 2]);
+    assert!(matches!(res, Err(Error::Eof)));
+}
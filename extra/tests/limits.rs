@@ -0,0 +1,117 @@
+mod common;
+
+use common::{Error, Reader, write};
+use represent::{AnalyzeType, Maker};
+use represent_derive::{AnalyzeWith, MakeWith, VisitWith};
+use represent_extra::{
+    generics::{
+        limits::{DecodeLimits, LimitError},
+        optional::Optional,
+    },
+    typedefs::{ArrMake, BigArrMake, RepeatMake},
+};
+
+fn limited(bytes: &[u8], limits: DecodeLimits) -> Reader {
+    let mut reader = Reader::new(bytes);
+    reader.limits = Some(limits);
+    reader
+}
+
+#[test]
+fn hostile_length_is_rejected_before_allocation() {
+    let bytes = [0xff, 0xff, 0xff, 0x7f];
+    let mut reader = limited(&bytes, DecodeLimits::new(1024, usize::MAX, 8));
+    let res: Result<BigArrMake<u32, u32>, _> = reader.make();
+    assert!(matches!(
+        res,
+        Err(Error::Limit(LimitError::TooManyBytes { max: 1024, .. }))
+    ));
+
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, 16, 8));
+    let res: Result<RepeatMake<u32, u8>, _> = reader.make();
+    assert!(matches!(
+        res,
+        Err(Error::Limit(LimitError::TooManyElements { max: 16, .. }))
+    ));
+}
+
+#[test]
+fn total_bytes_are_accumulated() {
+    let bytes = [2, 1, 2, 2, 3, 4];
+    let mut reader = limited(&bytes, DecodeLimits::new(3, usize::MAX, 8));
+    let first: BigArrMake<u8, u8> = reader.make().unwrap();
+    assert_eq!(first.0, [1, 2]);
+    let res: Result<BigArrMake<u8, u8>, _> = reader.make();
+    assert!(matches!(
+        res,
+        Err(Error::Limit(LimitError::TooManyBytes { allocated: 2, .. }))
+    ));
+}
+
+type Nested = RepeatMake<u8, RepeatMake<u8, RepeatMake<u8, u8>>>;
+
+#[test]
+fn nesting_depth() {
+    let bytes = [1, 1, 1, 0];
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, usize::MAX, 2));
+    let res: Result<Nested, _> = reader.make();
+    assert!(matches!(
+        res,
+        Err(Error::Limit(LimitError::TooDeep { max: 2 }))
+    ));
+
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, usize::MAX, 3));
+    let res: Nested = reader.make().unwrap();
+    assert_eq!(res.0[0].0[0].0, [0]);
+}
+
+#[derive(Debug, MakeWith, VisitWith, AnalyzeWith)]
+struct Node {
+    value: u8,
+    #[represent(recursive)]
+    children: RepeatMake<u8, Node>,
+}
+
+#[test]
+fn recursive_depth() {
+    let bytes = [1, 1, 2, 0];
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, usize::MAX, 0));
+    let res: Result<Node, _> = reader.make();
+    assert!(matches!(
+        res,
+        Err(Error::Limit(LimitError::TooDeep { max: 0 }))
+    ));
+
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, usize::MAX, 1));
+    let node: Node = reader.make().unwrap();
+    assert_eq!(node.children.0[0].value, 2);
+    assert_eq!(reader.type_dynamic_size(&node), bytes.len());
+    assert_eq!(write(&node).unwrap(), bytes);
+}
+
+#[derive(Debug, MakeWith, VisitWith, AnalyzeWith)]
+struct Tree {
+    value: u8,
+    #[represent(recursive)]
+    next: Optional<Box<Tree>>,
+    #[represent(recursive)]
+    children: ArrMake<u8, Tree>,
+}
+
+#[test]
+fn recursive_optional_and_arr() {
+    let bytes = [1, 1, 2, 0, 0, 1, 3, 0, 0];
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, usize::MAX, 0));
+    let res: Result<Tree, _> = reader.make();
+    assert!(matches!(
+        res,
+        Err(Error::Limit(LimitError::TooDeep { max: 0 }))
+    ));
+
+    let mut reader = limited(&bytes, DecodeLimits::new(usize::MAX, usize::MAX, 1));
+    let tree: Tree = reader.make().unwrap();
+    assert_eq!(tree.next.0.as_ref().unwrap().value, 2);
+    assert_eq!(tree.children.0[0].value, 3);
+    assert_eq!(reader.type_dynamic_size(&tree), bytes.len());
+    assert_eq!(write(&tree).unwrap(), bytes);
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{Error, read, write};
+use represent_extra::{
+    generics::{
+        blob::{BigStr, CStr, CStrError},
+        encoding::{Ascii, EncodingError, Latin1, Utf16Be, Utf16Le},
+        length::LengthError,
+        padding::{NulTerminatedPadded, PaddedStrError, SpacePadded},
+    },
+    typedefs::{BigStrMake, PaddedStaticStr},
+};
+
+#[test]
+fn c_str_roundtrip() {
+    let name: CStr = read(*b"name\0rest").unwrap();
+    assert_eq!(name.as_bytes(), b"name");
+    assert_eq!(write(&name).unwrap(), b"name\0");
+
+    let res: Result<CStr<3>, _> = read(*b"name\0");
+    assert!(matches!(
+        res,
+        Err(Error::CStr(CStrError::TooLong { max: 3, .. }))
+    ));
+    let res: Result<CStr, _> = read(*b"name");
+    assert!(matches!(res, Err(Error::Eof)));
+}
+
+#[test]
+fn c_str_interior_nul() {
+    assert!(matches!(
+        CStr::<8>::new(b"na\0me".to_vec()),
+        Err(CStrError::InteriorNul { position: 2 })
+    ));
+    let res = write(&CStr::<8>(b"na\0me".to_vec()));
+    assert!(matches!(
+        res,
+        Err(Error::CStr(CStrError::InteriorNul { position: 2 }))
+    ));
+}
+
+#[test]
+fn big_str_encodings() {
+    let latin1: BigStrMake<u8, Latin1> = read([2, b'a', 0xe9]).unwrap();
+    assert_eq!(latin1.as_str().unwrap(), "aé");
+    assert_eq!(write(&latin1).unwrap(), [2, b'a', 0xe9]);
+    let ascii: BigStrMake<u8, Ascii> = BigStr::new_unchecked(latin1.as_bytes().to_vec());
+    assert!(matches!(
+        ascii.as_str(),
+        Err(EncodingError::Invalid { position: 1, .. })
+    ));
+
+    let utf16: BigStrMake<u8, Utf16Be> = "aé".parse().unwrap();
+    assert_eq!(utf16.as_bytes(), [0, b'a', 0, 0xe9]);
+    let utf16: BigStrMake<u8, Utf16Le> = read([4, b'a', 0, 0xe9, 0]).unwrap();
+    assert_eq!(utf16.as_str().unwrap(), "aé");
+
+    assert!(matches!(
+        "€".parse::<BigStrMake<u8, Latin1>>(),
+        Err(EncodingError::Unrepresentable { ch: '€', .. })
+    ));
+    let utf8: BigStrMake<u8> = BigStr::new_unchecked(vec![b'a', 0xff]);
+    assert!(utf8.as_str().is_err());
+    assert_eq!(format!("{:?}", utf8), "{\"bytes\": [97, 255]}");
+}
+
+#[test]
+fn padded_str_policies() {
+    let zero: PaddedStaticStr<6> = read(*b"name\0\0").unwrap();
+    assert_eq!(zero.0, "name");
+    assert_eq!(write(&zero).unwrap(), b"name\0\0");
+
+    let space: PaddedStaticStr<6, SpacePadded> = read(*b"name  ").unwrap();
+    assert_eq!(space.0, "name");
+    assert_eq!(write(&space).unwrap(), b"name  ");
+
+    let nul: PaddedStaticStr<6, NulTerminatedPadded> = read(*b"na\0me\0").unwrap();
+    assert_eq!(nul.0, "na");
+    assert_eq!(write(&nul).unwrap(), b"na\0\0\0\0");
+
+    let wide: PaddedStaticStr<6, SpacePadded, Utf16Le> = read(*b"a\0 \0 \0").unwrap();
+    assert_eq!(wide.0, "a");
+    assert_eq!(write(&wide).unwrap(), b"a\0 \0 \0");
+}
+
+#[test]
+fn padded_str_unterminated() {
+    let res: Result<PaddedStaticStr<4, NulTerminatedPadded>, _> = read(*b"name");
+    assert!(matches!(
+        res,
+        Err(Error::PaddedStr(PaddedStrError::Unterminated { len: 4 }))
+    ));
+}
+
+#[test]
+fn padded_str_too_long() {
+    let full = PaddedStaticStr::<4>::new("name");
+    assert_eq!(write(&full).unwrap(), b"name");
+    let full = PaddedStaticStr::<4, NulTerminatedPadded>::new("name");
+    assert!(matches!(
+        write(&full),
+        Err(Error::PaddedStr(PaddedStrError::Length(
+            LengthError::Verify {
+                from: 5,
+                verified: 4
+            }
+        )))
+    ));
+    let res = write(&PaddedStaticStr::<3, SpacePadded>::new("name"));
+    assert!(matches!(
+        res,
+        Err(Error::PaddedStr(PaddedStrError::Length(_)))
+    ));
+}
@@ -0,0 +1,291 @@
+use std::{borrow::Cow, convert::TryInto, rc::Rc, sync::Arc};
+
+use crate::{
+    AnalyzeRecursive, AnalyzeStr, AnalyzeType, AnalyzeWith, MakeRecursive, MakeStr, MakeType,
+    MakeWith, Maker, RepresentKey, SizeOverflow, TypeAnalyzer, TypeSize, VisitRecursive, VisitStr,
+    VisitType, VisitWith, Visitor, checked_repeat_size, checked_sum, const_repeat_size, sum_sizes,
+};
+
+// region: Array
+
+impl<M, T, const N: usize> MakeWith<M> for [T; N]
+where
+    M: Maker + MakeType<T>,
+{
+    fn make_with(maker: &mut M) -> Result<[T; N], M::Error> {
+        let mut vec = Vec::<T>::with_capacity(N);
+        for index in 0..N {
+            vec.push(maker.make_keyed(index)?);
+        }
+        match vec.try_into() {
+            Ok(arr) => Ok(arr),
+            Err(_) => unreachable!("Vec should have exactly {} elements", N),
+        }
+    }
+}
+
+impl<V, T, const N: usize> VisitWith<V> for [T; N]
+where
+    V: Visitor + VisitType<T>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        for (index, element) in self.iter().enumerate() {
+            visitor.visit_keyed(index, element)?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: TypeAnalyzer + AnalyzeType<T>, T, const N: usize> AnalyzeWith<A> for [T; N] {
+    const CONST_SIZE: TypeSize = repeat_size(<A as AnalyzeType<T>>::TYPE_CONST_SIZE, N);
+
+    crate::expect_sizes!(A);
+
+    fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+        checked_repeat_size(0, N, <A as AnalyzeType<T>>::type_try_fixed_size(analyzer)?)
+    }
+
+    fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+        checked_sum(
+            self.iter()
+                .map(|element| analyzer.type_try_dynamic_size(element)),
+        )
+    }
+}
+
+const fn repeat_size(single: TypeSize, reps: usize) -> TypeSize {
+    match single {
+        TypeSize::Const(bytes) => TypeSize::Const(const_repeat_size(0, reps, bytes)),
+        _ if reps == 0 => TypeSize::Const(0),
+        other => other,
+    }
+}
+
+// endregion
+// region: Tuple
+
+macro_rules! impl_tuple {
+    ($($T:ident $index:tt),+) => {
+        impl<M, $($T),+> MakeWith<M> for ($($T,)+)
+        where
+            M: Maker $(+ MakeType<$T>)+,
+        {
+            fn make_with(maker: &mut M) -> Result<($($T,)+), M::Error> {
+                Ok(($(maker.make_keyed(RepresentKey::Usize($index))?,)+))
+            }
+        }
+
+        impl<V, $($T),+> VisitWith<V> for ($($T,)+)
+        where
+            V: Visitor $(+ VisitType<$T>)+,
+        {
+            fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+                $(visitor.visit_keyed(RepresentKey::Usize($index), &self.$index)?;)+
+                Ok(())
+            }
+        }
+
+        impl<A, $($T),+> AnalyzeWith<A> for ($($T,)+)
+        where
+            A: TypeAnalyzer $(+ AnalyzeType<$T>)+,
+        {
+            const CONST_SIZE: TypeSize =
+                sum_sizes([$(<A as AnalyzeType<$T>>::TYPE_CONST_SIZE),+]);
+
+            crate::expect_sizes!(A);
+
+            fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+                checked_sum([$(<A as AnalyzeType<$T>>::type_try_fixed_size(analyzer)),+])
+            }
+
+            fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+                checked_sum([$(analyzer.type_try_dynamic_size(&self.$index)),+])
+            }
+        }
+    };
+}
+
+impl_tuple!(T0 0);
+impl_tuple!(T0 0, T1 1);
+impl_tuple!(T0 0, T1 1, T2 2);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+
+// endregion
+// region: Pointer
+
+macro_rules! impl_pointer {
+    ($($P:ident),+) => {$(
+        impl<M, T> MakeWith<M> for $P<T>
+        where
+            M: Maker + MakeType<T>,
+        {
+            fn make_with(maker: &mut M) -> Result<$P<T>, M::Error> {
+                Ok($P::new(maker.make_type()?))
+            }
+        }
+
+        impl<V, T> VisitWith<V> for $P<T>
+        where
+            V: Visitor + VisitType<T>,
+        {
+            fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+                visitor.visit(&**self)
+            }
+        }
+
+        impl<A: TypeAnalyzer + AnalyzeType<T>, T> AnalyzeWith<A> for $P<T> {
+            const CONST_SIZE: TypeSize = <A as AnalyzeType<T>>::TYPE_CONST_SIZE;
+
+            fn fixed_size(analyzer: &A) -> usize {
+                <A as AnalyzeType<T>>::type_fixed_size(analyzer)
+            }
+
+            fn dynamic_size(&self, analyzer: &A) -> usize {
+                analyzer.type_dynamic_size(&**self)
+            }
+
+            fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+                <A as AnalyzeType<T>>::type_try_fixed_size(analyzer)
+            }
+
+            fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+                analyzer.type_try_dynamic_size(&**self)
+            }
+        }
+
+        impl<M: Maker, T> MakeRecursive<M, T> for $P<T> {
+            fn make_recursive(
+                maker: &mut M,
+                mut element: impl FnMut(&mut M) -> Result<T, M::Error>,
+            ) -> Result<$P<T>, M::Error> {
+                Ok($P::new(element(maker)?))
+            }
+        }
+
+        impl<V: Visitor, T> VisitRecursive<V, T> for $P<T> {
+            fn visit_recursive(
+                &self,
+                visitor: &mut V,
+                mut element: impl FnMut(&T, &mut V) -> Result<(), V::Error>,
+            ) -> Result<(), V::Error> {
+                element(&**self, visitor)
+            }
+        }
+
+        impl<A: TypeAnalyzer, T> AnalyzeRecursive<A, T> for $P<T> {
+            fn try_recursive_size(
+                &self,
+                analyzer: &A,
+                mut element: impl FnMut(&T, &A) -> Result<usize, SizeOverflow>,
+            ) -> Result<usize, SizeOverflow> {
+                element(&**self, analyzer)
+            }
+        }
+    )+};
+}
+
+impl_pointer!(Box, Rc, Arc);
+
+// endregion
+// region: Cow
+
+impl<'a, M, B> MakeWith<M> for Cow<'a, B>
+where
+    B: Clone,
+    M: Maker + MakeType<B>,
+{
+    fn make_with(maker: &mut M) -> Result<Cow<'a, B>, M::Error> {
+        Ok(Cow::Owned(maker.make_type()?))
+    }
+}
+
+impl<'a, V, B> VisitWith<V> for Cow<'a, B>
+where
+    B: Clone,
+    V: Visitor + VisitType<B>,
+{
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit(&**self)
+    }
+}
+
+impl<'a, A, B> AnalyzeWith<A> for Cow<'a, B>
+where
+    B: Clone,
+    A: TypeAnalyzer + AnalyzeType<B>,
+{
+    const CONST_SIZE: TypeSize = <A as AnalyzeType<B>>::TYPE_CONST_SIZE;
+
+    fn fixed_size(analyzer: &A) -> usize {
+        <A as AnalyzeType<B>>::type_fixed_size(analyzer)
+    }
+
+    fn dynamic_size(&self, analyzer: &A) -> usize {
+        analyzer.type_dynamic_size(&**self)
+    }
+
+    fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+        <A as AnalyzeType<B>>::type_try_fixed_size(analyzer)
+    }
+
+    fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+        analyzer.type_try_dynamic_size(&**self)
+    }
+}
+
+impl<'a, M: MakeStr> MakeWith<M> for Cow<'a, str> {
+    fn make_with(maker: &mut M) -> Result<Cow<'a, str>, M::Error> {
+        maker.make_str().map(Cow::Owned)
+    }
+}
+
+impl<'a, V: VisitStr> VisitWith<V> for Cow<'a, str> {
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit_str(self)
+    }
+}
+
+impl<'a, A: AnalyzeStr> AnalyzeWith<A> for Cow<'a, str> {
+    const CONST_SIZE: TypeSize = TypeSize::Dynamic;
+
+    crate::expect_sizes!(dynamic A);
+
+    fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+        analyzer.try_str_size(self)
+    }
+}
+
+// endregion
+// region: String
+
+impl<M: MakeStr> MakeWith<M> for String {
+    fn make_with(maker: &mut M) -> Result<String, M::Error> {
+        maker.make_str()
+    }
+}
+
+impl<V: VisitStr> VisitWith<V> for String {
+    fn visit_with(&self, visitor: &mut V) -> Result<(), V::Error> {
+        visitor.visit_str(self)
+    }
+}
+
+impl<A: AnalyzeStr> AnalyzeWith<A> for String {
+    const CONST_SIZE: TypeSize = TypeSize::Dynamic;
+
+    crate::expect_sizes!(dynamic A);
+
+    fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+        analyzer.try_str_size(self)
+    }
+}
+
+// endregion
@@ -1,5 +1,6 @@
+mod impls;
 mod key;
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData};
 
 pub use key::RepresentKey;
 #[cfg(feature = "derive")]
@@ -62,6 +63,13 @@ pub trait Maker {
     {
         self.with_key(key.into(), |maker| maker.make_type())
     }
+    /// wraps every value made through [`MakeRecursive`], backends may limit the nesting depth here
+    fn recurse<R>(
+        &mut self,
+        func: impl FnOnce(&mut Self) -> Result<R, Self::Error>,
+    ) -> Result<R, Self::Error> {
+        func(self)
+    }
 }
 pub trait MakeType<T: Sized>: Maker {
     fn make_type(&mut self) -> Result<T, <Self as Maker>::Error>;
@@ -71,6 +79,48 @@ pub trait MakeWith<M: Maker>: Sized {
 }
 pub trait TypeAnalyzer {}
 
+/// Backend encoding of strings, `String` and `Cow<str>` are made through it.
+pub trait MakeStr: Maker {
+    fn make_str(&mut self) -> Result<String, Self::Error>;
+}
+
+/// Visiting counterpart of [`MakeStr`].
+pub trait VisitStr: Visitor {
+    fn visit_str(&mut self, target: &str) -> Result<(), Self::Error>;
+}
+
+/// Sizing counterpart of [`MakeStr`], strings are always `Dynamic`.
+pub trait AnalyzeStr: TypeAnalyzer {
+    fn try_str_size(&self, target: &str) -> Result<usize, SizeOverflow>;
+}
+
+/// Container that makes its `T` elements with the given function instead of `MakeType<T>`,
+/// so a type can contain itself without its own impl requiring itself.
+pub trait MakeRecursive<M: Maker, T>: Sized {
+    fn make_recursive(
+        maker: &mut M,
+        element: impl FnMut(&mut M) -> Result<T, <M as Maker>::Error>,
+    ) -> Result<Self, <M as Maker>::Error>;
+}
+
+/// Visiting counterpart of [`MakeRecursive`].
+pub trait VisitRecursive<V: Visitor, T> {
+    fn visit_recursive(
+        &self,
+        visitor: &mut V,
+        element: impl FnMut(&T, &mut V) -> Result<(), <V as Visitor>::Error>,
+    ) -> Result<(), <V as Visitor>::Error>;
+}
+
+/// Sizing counterpart of [`MakeRecursive`], recursive types are always `Dynamic`.
+pub trait AnalyzeRecursive<A: TypeAnalyzer, T> {
+    fn try_recursive_size(
+        &self,
+        analyzer: &A,
+        element: impl FnMut(&T, &A) -> Result<usize, SizeOverflow>,
+    ) -> Result<usize, SizeOverflow>;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TypeSize {
     Const(usize),
@@ -91,6 +141,67 @@ impl TypeSize {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeOverflow;
+
+impl fmt::Display for SizeOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("size of the type overflows usize")
+    }
+}
+
+impl std::error::Error for SizeOverflow {}
+
+/// Tag of a fieldless `#[repr]` enum that matches none of its discriminants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDiscriminant {
+    pub value: i128,
+    pub type_name: &'static str,
+}
+
+impl fmt::Display for UnknownDiscriminant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown discriminant {} of {}",
+            self.value, self.type_name
+        )
+    }
+}
+
+impl std::error::Error for UnknownDiscriminant {}
+
+/// `header + reps * single`, checked for overflow
+pub fn checked_repeat_size(
+    header: usize,
+    reps: usize,
+    single: usize,
+) -> Result<usize, SizeOverflow> {
+    reps.checked_mul(single)
+        .and_then(|body| body.checked_add(header))
+        .ok_or(SizeOverflow)
+}
+
+/// const counterpart of [`checked_repeat_size`], fails const evaluation on overflow
+pub const fn const_repeat_size(header: usize, reps: usize, single: usize) -> usize {
+    match reps.checked_mul(single) {
+        Some(body) => match body.checked_add(header) {
+            Some(size) => size,
+            None => panic!("Const size overflows usize"),
+        },
+        None => panic!("Const size overflows usize"),
+    }
+}
+
+pub fn checked_sum<I>(sizes: I) -> Result<usize, SizeOverflow>
+where
+    I: IntoIterator<Item = Result<usize, SizeOverflow>>,
+{
+    sizes.into_iter().try_fold(0usize, |sum, size| {
+        sum.checked_add(size?).ok_or(SizeOverflow)
+    })
+}
+
 pub struct AssertConstOrFixed<A: TypeAnalyzer, S: AnalyzeWith<A>>(PhantomData<A>, PhantomData<S>);
 impl<A: TypeAnalyzer, S: AnalyzeWith<A>> AssertConstOrFixed<A, S> {
     const ASSERT_CONST_OR_FIXED: () = assert!(S::CONST_SIZE.is_const_or_fixed());
@@ -121,6 +232,14 @@ pub trait AnalyzeType<T>: TypeAnalyzer {
             TypeSize::Dynamic => unimplemented!("dynamic_size() should be implemented manually!"),
         }
     }
+
+    fn type_try_fixed_size(&self) -> Result<usize, SizeOverflow> {
+        Ok(self.type_fixed_size())
+    }
+
+    fn type_try_dynamic_size(&self, target: &T) -> Result<usize, SizeOverflow> {
+        Ok(self.type_dynamic_size(target))
+    }
 }
 
 pub trait AnalyzeWith<A: TypeAnalyzer> {
@@ -143,6 +262,57 @@ pub trait AnalyzeWith<A: TypeAnalyzer> {
             TypeSize::Dynamic => unimplemented!("dynamic_size() should be implemented manually!"),
         }
     }
+
+    /// types with computed sizes should override it together with `fixed_size`
+    fn try_fixed_size(analyzer: &A) -> Result<usize, SizeOverflow> {
+        Ok(Self::fixed_size(analyzer))
+    }
+
+    /// types with computed sizes should override it together with `dynamic_size`
+    fn try_dynamic_size(&self, analyzer: &A) -> Result<usize, SizeOverflow> {
+        Ok(self.dynamic_size(analyzer))
+    }
+}
+
+/// Implements `fixed_size` and `dynamic_size` of [`AnalyzeWith`] by expecting their `try_*`
+/// counterparts, for impls that only compute checked sizes.
+#[macro_export]
+macro_rules! expect_sizes {
+    ($A:ty) => {
+        $crate::expect_sizes!(fixed $A);
+        $crate::expect_sizes!(dynamic $A);
+    };
+    (fixed $A:ty) => {
+        fn fixed_size(analyzer: &$A) -> usize {
+            <Self as $crate::AnalyzeWith<$A>>::try_fixed_size(analyzer).expect("size overflow")
+        }
+    };
+    (dynamic $A:ty) => {
+        fn dynamic_size(&self, analyzer: &$A) -> usize {
+            <Self as $crate::AnalyzeWith<$A>>::try_dynamic_size(self, analyzer)
+                .expect("size overflow")
+        }
+    };
+}
+
+/// size of a sum type, `Const` only if every alternative has the same const size
+pub const fn same_sizes<const L: usize>(sizes: [TypeSize; L]) -> TypeSize {
+    if L == 0 {
+        return TypeSize::Const(0);
+    }
+    let first = match sizes[0] {
+        TypeSize::Const(size) => size,
+        _ => return TypeSize::Dynamic,
+    };
+    let mut i = 1;
+    while i < L {
+        match sizes[i] {
+            TypeSize::Const(size) if size == first => {}
+            _ => return TypeSize::Dynamic,
+        }
+        i += 1;
+    }
+    TypeSize::Const(first)
 }
 
 pub const fn sum_sizes<const L: usize>(sizes: [TypeSize; L]) -> TypeSize {
@@ -150,9 +320,10 @@ pub const fn sum_sizes<const L: usize>(sizes: [TypeSize; L]) -> TypeSize {
     let mut size = TypeSize::Const(0);
     while i < L {
         match (size, sizes[i]) {
-            (TypeSize::Const(sum), TypeSize::Const(add)) => {
-                size = TypeSize::Const(sum + add);
-            }
+            (TypeSize::Const(sum), TypeSize::Const(add)) => match sum.checked_add(add) {
+                Some(sum) => size = TypeSize::Const(sum),
+                None => panic!("Const size overflows usize"),
+            },
             (TypeSize::Const(_), TypeSize::Fixed) => {
                 size = TypeSize::Fixed;
             }